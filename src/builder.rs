@@ -1,17 +1,31 @@
 use super::GlyphBrush;
-use crate::GlyphBrushBackend;
+use crate::{
+    layout::Faces, FontFace, FontFamilies, FontStyle, FontWeight, GlyphBrushBackend, Overflow,
+    ShapedLayout, Spacing,
+};
 use core::hash::BuildHasher;
-use glyph_brush::{ab_glyph::Font, delegate_glyph_brush_builder_fns, DefaultSectionHasher};
+use glyph_brush::{
+    ab_glyph::{Font, FontArc},
+    delegate_glyph_brush_builder_fns, DefaultSectionHasher, FontId,
+};
 use luminance::context::GraphicsContext;
 
 /// Builder for a [`GlyphBrush`](struct.GlyphBrush.html).
 pub struct GlyphBrushBuilder<F, H = DefaultSectionHasher> {
     inner: glyph_brush::GlyphBrushBuilder<F, H>,
+    shaping: Option<ShapedLayout>,
+    families: FontFamilies,
+    faces: Faces,
 }
 
 impl<F, H> From<glyph_brush::GlyphBrushBuilder<F, H>> for GlyphBrushBuilder<F, H> {
     fn from(inner: glyph_brush::GlyphBrushBuilder<F, H>) -> Self {
-        GlyphBrushBuilder { inner }
+        GlyphBrushBuilder {
+            inner,
+            shaping: None,
+            families: FontFamilies::default(),
+            faces: Faces::default(),
+        }
     }
 }
 
@@ -24,9 +38,13 @@ impl GlyphBrushBuilder<()> {
     }
 
     pub fn using_fonts<F: Font>(fonts: Vec<F>) -> GlyphBrushBuilder<F> {
-        GlyphBrushBuilder {
-            inner: glyph_brush::GlyphBrushBuilder::using_fonts(fonts),
-        }
+        glyph_brush::GlyphBrushBuilder::using_fonts(fonts).into()
+    }
+
+    /// Specifies the default font by its face, registered for shaping, see
+    /// [`font_face`](#method.font_face).
+    pub fn using_font_face(face: FontFace) -> GlyphBrushBuilder<FontArc> {
+        Self::using_font(face.font()).font_face(FontId(0), face)
     }
 }
 
@@ -57,15 +75,110 @@ impl<F: Font, H: BuildHasher> GlyphBrushBuilder<F, H> {
     pub fn section_hasher<T: BuildHasher>(self, section_hasher: T) -> GlyphBrushBuilder<F, T> {
         GlyphBrushBuilder {
             inner: self.inner.section_hasher(section_hasher),
+            shaping: self.shaping,
+            families: self.families,
+            faces: self.faces,
         }
     }
 
+    /// Sets whether sections queued with their own built-in
+    /// [`Layout`](enum.Layout.html) are laid out with a
+    /// [`ShapedLayout`](struct.ShapedLayout.html) instead, applying complex
+    /// text shaping to them.
+    ///
+    /// Also applies to measurements made through
+    /// [`GlyphCruncher`](trait.GlyphCruncher.html) with a built-in layout.
+    /// Only fonts with a [registered face](#method.font_face) are shaped.
+    ///
+    /// Defaults to `false`.
+    pub fn shaping(mut self, shaping: bool) -> Self {
+        self.shaping = match shaping {
+            true => self.shaping.or_else(|| Some(ShapedLayout::default())),
+            false => None,
+        };
+        self
+    }
+
+    /// Registers the face of the font `font_id` for shaping, see
+    /// [`GlyphBrush::set_font_face`](struct.GlyphBrush.html#method.set_font_face).
+    ///
+    /// Enables [`shaping`](#method.shaping).
+    pub fn font_face(mut self, font_id: FontId, face: FontFace) -> Self {
+        self.shaping = Some(
+            self.shaping
+                .unwrap_or_default()
+                .font_face(font_id, face.clone()),
+        );
+        self.faces.insert(font_id, face);
+        self
+    }
+
+    /// Sets the font fallback chain of sections laid out with the built-in
+    /// [`Layout`](enum.Layout.html), see
+    /// [`ShapedLayout::fallback_fonts`](struct.ShapedLayout.html#method.fallback_fonts).
+    ///
+    /// Enables [`shaping`](#method.shaping).
+    pub fn fallback_fonts(mut self, fonts: Vec<FontId>) -> Self {
+        self.shaping = Some(self.shaping.unwrap_or_default().fallback_fonts(fonts));
+        self
+    }
+
+    /// Sets the letter spacing, word spacing & line height of sections laid
+    /// out with the built-in [`Layout`](enum.Layout.html), see
+    /// [`ShapedLayout::spacing`](struct.ShapedLayout.html#method.spacing).
+    ///
+    /// Enables [`shaping`](#method.shaping).
+    pub fn spacing(mut self, spacing: Spacing) -> Self {
+        self.shaping = Some(self.shaping.unwrap_or_default().spacing(spacing));
+        self
+    }
+
+    /// Sets how sections laid out with the built-in
+    /// [`Layout`](enum.Layout.html) handle text overflowing their bounds, see
+    /// [`ShapedLayout::overflow`](struct.ShapedLayout.html#method.overflow).
+    ///
+    /// Enables [`shaping`](#method.shaping).
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.shaping = Some(self.shaping.unwrap_or_default().overflow(overflow));
+        self
+    }
+
+    /// Sets whether full stops & commas hang past the end of wrapped lines of
+    /// sections laid out with the built-in [`Layout`](enum.Layout.html), see
+    /// [`ShapedLayout::hanging_punctuation`](struct.ShapedLayout.html#method.hanging_punctuation).
+    ///
+    /// Enables [`shaping`](#method.shaping).
+    pub fn hanging_punctuation(mut self, hanging_punctuation: bool) -> Self {
+        self.shaping = Some(
+            self.shaping
+                .unwrap_or_default()
+                .hanging_punctuation(hanging_punctuation),
+        );
+        self
+    }
+
+    /// Adds a font as the face of a family for a weight & style, see
+    /// [`GlyphBrush::add_font_variant`](struct.GlyphBrush.html#method.add_font_variant).
+    ///
+    /// Returns a new [`FontId`](struct.FontId.html) to reference this font.
+    pub fn add_font_variant(
+        &mut self,
+        family: &str,
+        weight: FontWeight,
+        style: FontStyle,
+        font: F,
+    ) -> FontId {
+        let font_id = self.inner.add_font(font);
+        self.families.insert(family, weight, style, font_id);
+        font_id
+    }
+
     /// Builds a `GlyphBrush` in the given `glow::Context`.
     pub fn build<C>(self, context: &mut C) -> GlyphBrush<C::Backend, F, H>
     where
         C: GraphicsContext,
         C::Backend: GlyphBrushBackend,
     {
-        GlyphBrush::new(context, self.inner)
+        GlyphBrush::new(context, self.inner, self.shaping, self.families, self.faces)
     }
 }
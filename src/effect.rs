@@ -0,0 +1,142 @@
+use std::hash::{Hash, Hasher};
+
+/// Shortest wavelength in pixels, the shader dividing by it.
+const MIN_WAVELENGTH: f32 = 1.0;
+
+/// Animation of the glyphs of a `Text`, evaluated in the vertex shader each
+/// frame at the time set by
+/// [`GlyphBrush::set_effect_time`](struct.GlyphBrush.html#method.set_effect_time),
+/// see [`GlyphBrush::queue_with_effects`](struct.GlyphBrush.html#method.queue_with_effects).
+///
+/// Glyphs are animated at a phase along the text given by their horizontal
+/// position, without laying the section out again.
+///
+/// ```
+/// # use luminance_glyph::Effect;
+/// let wobble = Effect::Wave {
+///     amplitude: 3.0,
+///     wavelength: 80.0,
+///     speed: 1.5,
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Effect {
+    #[default]
+    None,
+    /// Glyphs bob up & down `amplitude` pixels, in a wave `wavelength`
+    /// pixels long travelling along the text at `speed` waves per second.
+    ///
+    /// Wavelengths below 1 pixel, including `0` & NaN, are clamped to 1.
+    Wave {
+        amplitude: f32,
+        wavelength: f32,
+        speed: f32,
+    },
+    /// Glyphs jitter up to `amplitude` pixels, `rate` times per second.
+    Shake { amplitude: f32, rate: f32 },
+    /// Glyph colors cycle through the hues, repeating every `wavelength`
+    /// pixels along the text & `speed` times per second. Alpha is kept.
+    ///
+    /// Wavelengths below 1 pixel, including `0` & NaN, are clamped to 1.
+    Rainbow { wavelength: f32, speed: f32 },
+}
+
+impl Effect {
+    /// Returns the effect's id in the vertex shader.
+    #[inline]
+    pub(crate) fn id(&self) -> u32 {
+        match self {
+            Effect::None => 0,
+            Effect::Wave { .. } => 1,
+            Effect::Shake { .. } => 2,
+            Effect::Rainbow { .. } => 3,
+        }
+    }
+
+    /// Returns the effect's parameters in the vertex shader.
+    #[inline]
+    pub(crate) fn params(&self) -> [f32; 4] {
+        match *self {
+            Effect::None => [0.0; 4],
+            Effect::Wave {
+                amplitude,
+                wavelength,
+                speed,
+            } => [amplitude, wavelength.max(MIN_WAVELENGTH), speed, 0.0],
+            Effect::Shake { amplitude, rate } => [amplitude, rate, 0.0, 0.0],
+            Effect::Rainbow { wavelength, speed } => {
+                [wavelength.max(MIN_WAVELENGTH), speed, 0.0, 0.0]
+            }
+        }
+    }
+}
+
+impl PartialEq for Effect {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+            && self.params().map(f32::to_bits) == other.params().map(f32::to_bits)
+    }
+}
+
+impl Eq for Effect {}
+
+impl Hash for Effect {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+        self.params().map(f32::to_bits).hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wavelength(effect: Effect) -> f32 {
+        match effect {
+            Effect::Rainbow { .. } => effect.params()[0],
+            _ => effect.params()[1],
+        }
+    }
+
+    #[test]
+    fn clamps_wavelengths() {
+        for &(given, clamped) in &[(80.0, 80.0), (0.5, 1.0), (0.0, 1.0), (-3.0, 1.0)] {
+            let wave = Effect::Wave {
+                amplitude: 3.0,
+                wavelength: given,
+                speed: 1.0,
+            };
+            let rainbow = Effect::Rainbow {
+                wavelength: given,
+                speed: 1.0,
+            };
+            assert_eq!(wavelength(wave), clamped);
+            assert_eq!(wavelength(rainbow), clamped);
+        }
+
+        let nan = Effect::Rainbow {
+            wavelength: f32::NAN,
+            speed: 1.0,
+        };
+        assert_eq!(wavelength(nan), 1.0);
+        assert_eq!(
+            nan,
+            Effect::Rainbow {
+                wavelength: 1.0,
+                speed: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn compares_effects_by_id_and_params() {
+        let shake = Effect::Shake {
+            amplitude: 0.0,
+            rate: 0.0,
+        };
+        assert_ne!(shake, Effect::None);
+        assert_eq!(Effect::default(), Effect::None);
+    }
+}
@@ -0,0 +1,97 @@
+use glyph_brush::{Extra, Section, Text};
+use std::hash::{Hash, Hasher};
+
+/// Per `Text` vertex generation data, extending the public
+/// [`Extra`](struct.Extra.html) with styling resolved from the `FontId`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GlyphExtra {
+    pub extra: Extra,
+    /// Horizontal shear of synthetic oblique fonts, see `Synthesis::skew`.
+    pub skew: f32,
+    /// Baseline y of the glyph sheared by `skew`, set per glyph.
+    pub baseline: f32,
+    /// Width in pixels over which glyphs fade out before the right bound,
+    /// see `Overflow::Fade`.
+    pub fade: f32,
+    /// Clockwise rotation in radians of each glyph quad about its center,
+    /// see `VerticalLayout`.
+    pub rotation: f32,
+    /// Column-major rotation & scale matrix about `pivot`, see
+    /// `SectionTransform`.
+    pub transform: [f32; 4],
+    pub pivot: [f32; 2],
+    /// Index of the section's 4x4 transform, see `TransformQueue`.
+    pub transform_index: u32,
+    /// World-space mode of the section transform, see `Billboard::mode`.
+    pub billboard: u32,
+    /// Draw layer of the section, see `GlyphBrush::set_layer`.
+    pub layer: u32,
+    /// 1-based index of the stamped section, `0` if not stamped, see
+    /// `GlyphBrush::queue_stamped`.
+    pub stamp: u32,
+    /// Id & parameters of the text's animation, see `Effect`.
+    pub effect: u32,
+    pub effect_params: [f32; 4],
+}
+
+impl From<Extra> for GlyphExtra {
+    #[inline]
+    fn from(extra: Extra) -> Self {
+        GlyphExtra {
+            extra,
+            skew: 0.0,
+            baseline: 0.0,
+            fade: 0.0,
+            rotation: 0.0,
+            transform: [1.0, 0.0, 0.0, 1.0],
+            pivot: [0.0, 0.0],
+            transform_index: 0,
+            billboard: 0,
+            layer: 0,
+            stamp: 0,
+            effect: 0,
+            effect_params: [0.0; 4],
+        }
+    }
+}
+
+impl Hash for GlyphExtra {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.extra.hash(state);
+        self.skew.to_bits().hash(state);
+        self.baseline.to_bits().hash(state);
+        self.fade.to_bits().hash(state);
+        self.rotation.to_bits().hash(state);
+        self.transform.map(f32::to_bits).hash(state);
+        self.pivot.map(f32::to_bits).hash(state);
+        self.transform_index.hash(state);
+        self.billboard.hash(state);
+        self.layer.hash(state);
+        self.stamp.hash(state);
+        self.effect.hash(state);
+        self.effect_params.map(f32::to_bits).hash(state);
+    }
+}
+
+/// Converts a section's extras with `extend`.
+pub(crate) fn extend_section<'a, E>(section: &Section<'a>, extend: E) -> Section<'a, GlyphExtra>
+where
+    E: Fn(&Text<'a>) -> GlyphExtra,
+{
+    Section {
+        screen_position: section.screen_position,
+        bounds: section.bounds,
+        layout: section.layout,
+        text: section
+            .text
+            .iter()
+            .map(|text| Text {
+                text: text.text,
+                scale: text.scale,
+                font_id: text.font_id,
+                extra: extend(text),
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,144 @@
+use glyph_brush::Section;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// Binary search steps, finding scales within 1/256 of the largest fitting.
+const SEARCH_STEPS: u32 = 8;
+
+/// Scale factors found by shrink-to-fit searches, by [`fit_hash`].
+///
+/// Factors unused during a frame are dropped when drawing the next one.
+#[derive(Debug, Default)]
+pub(crate) struct FitCache {
+    factors: HashMap<u64, (f32, bool)>,
+}
+
+impl FitCache {
+    /// Returns the cached scale factor of the section, or searches it.
+    pub fn factor<S>(&mut self, section_hash: u64, search: S) -> f32
+    where
+        S: FnOnce() -> f32,
+    {
+        let (factor, used) = self
+            .factors
+            .entry(section_hash)
+            .or_insert_with(|| (search(), true));
+        *used = true;
+        *factor
+    }
+
+    /// Drops factors unused since the last call.
+    pub fn trim(&mut self) {
+        self.factors.retain(|_, (_, used)| std::mem::take(used));
+    }
+}
+
+/// Hashes what the fitting scale of a section depends on: its texts, scales,
+/// fonts, bounds & layout, but not its position or colors, so that moving or
+/// recoloring a section keeps its factor.
+///
+/// Hashed with fixed keys, unlike the randomly seeded section hasher, so that
+/// equal sections queued in later frames hit the cache.
+pub(crate) fn fit_hash<X>(section: &Section<'_, X>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for text in &section.text {
+        text.text.hash(&mut hasher);
+        text.scale.x.to_bits().hash(&mut hasher);
+        text.scale.y.to_bits().hash(&mut hasher);
+        text.font_id.hash(&mut hasher);
+    }
+    section.bounds.0.to_bits().hash(&mut hasher);
+    section.bounds.1.to_bits().hash(&mut hasher);
+    section.layout.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the largest scale factor up to `1` at which `fits`, or the
+/// smallest one tried if none does.
+pub(crate) fn search<F>(mut fits: F) -> f32
+where
+    F: FnMut(f32) -> bool,
+{
+    if fits(1.0) {
+        return 1.0;
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..SEARCH_STEPS {
+        let factor = (low + high) / 2.0;
+        match fits(factor) {
+            true => low = factor,
+            false => high = factor,
+        }
+    }
+
+    if low > 0.0 {
+        low
+    } else {
+        high
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glyph_brush::{Extra, Text};
+
+    #[test]
+    fn keeps_fitting_sections() {
+        let mut tries = 0;
+        let factor = search(|_| {
+            tries += 1;
+            true
+        });
+        assert_eq!((factor, tries), (1.0, 1));
+    }
+
+    #[test]
+    fn finds_largest_fitting_factor() {
+        let factor = search(|factor| factor <= 0.3);
+        assert!(factor <= 0.3);
+        assert!(0.3 - factor < 1.0 / 256.0);
+
+        // exactly representable steps are found exactly
+        assert_eq!(search(|factor| factor <= 0.75), 0.75);
+    }
+
+    #[test]
+    fn falls_back_to_smallest_tried() {
+        assert_eq!(search(|_| false), 1.0 / 256.0);
+    }
+
+    #[test]
+    fn caches_factors_used_since_last_trim() {
+        let mut cache = FitCache::default();
+        assert_eq!(cache.factor(1, || 0.5), 0.5);
+        assert_eq!(cache.factor(1, || unreachable!()), 0.5);
+
+        cache.trim();
+        assert_eq!(cache.factor(2, || 0.25), 0.25);
+        cache.trim();
+        // unused during the last frame
+        assert_eq!(cache.factor(1, || 0.75), 0.75);
+        assert_eq!(cache.factor(2, || unreachable!()), 0.25);
+    }
+
+    #[test]
+    fn keys_factors_by_what_fits() {
+        let section = |text, position, color| Section {
+            screen_position: position,
+            bounds: (100.0, 20.0),
+            text: vec![Text::<Extra>::new(text)
+                .with_scale(30.0)
+                .with_extra(Extra { color, z: 0.0 })],
+            ..Section::default()
+        };
+        let key = fit_hash(&section("fit", (0.0, 0.0), [1.0; 4]));
+
+        assert_eq!(key, fit_hash(&section("fit", (5.0, 7.0), [1.0; 4])));
+        assert_eq!(key, fit_hash(&section("fit", (0.0, 0.0), [0.5; 4])));
+        assert_ne!(key, fit_hash(&section("fits", (0.0, 0.0), [1.0; 4])));
+    }
+}
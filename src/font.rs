@@ -0,0 +1,107 @@
+mod face;
+mod family;
+mod synthetic;
+mod system;
+mod variation;
+
+pub use face::FontFace;
+pub use family::{FontFamilies, FontFamily, FontMatch, FontVariant};
+pub use synthetic::Synthesis;
+pub(crate) use synthetic::SyntheticFont;
+pub use system::{LoadFontError, SystemFont, SystemFonts};
+pub use variation::Variation;
+pub(crate) use variation::VariationFont;
+
+/// Weight of a font face, from 1 to 1000 as in the OpenType `OS/2` table.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const EXTRA_LIGHT: FontWeight = FontWeight(200);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const NORMAL: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const SEMI_BOLD: FontWeight = FontWeight(600);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const EXTRA_BOLD: FontWeight = FontWeight(800);
+    pub const BLACK: FontWeight = FontWeight(900);
+
+    /// Parses a weight name such as `"Bold"` or `"ExtraLight"`, ignoring case,
+    /// spaces & hyphens.
+    pub fn from_name(name: &str) -> Option<FontWeight> {
+        let name: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        Some(match name.as_str() {
+            "thin" | "hairline" => FontWeight::THIN,
+            "extralight" | "ultralight" => FontWeight::EXTRA_LIGHT,
+            "light" => FontWeight::LIGHT,
+            "" | "regular" | "normal" | "book" | "roman" => FontWeight::NORMAL,
+            "medium" => FontWeight::MEDIUM,
+            "semibold" | "demibold" => FontWeight::SEMI_BOLD,
+            "bold" => FontWeight::BOLD,
+            "extrabold" | "ultrabold" => FontWeight::EXTRA_BOLD,
+            "black" | "heavy" => FontWeight::BLACK,
+            _ => return None,
+        })
+    }
+
+    /// Orders candidate weights by preference for this desired weight,
+    /// following the CSS font matching algorithm.
+    pub(crate) fn match_key(self, candidate: FontWeight) -> (u8, u16) {
+        let (desired, candidate) = (self.0, candidate.0);
+        if desired == candidate {
+            return (0, 0);
+        }
+
+        let prefer_lighter = match desired {
+            400..=500 if (desired..=500).contains(&candidate) => return (0, candidate - desired),
+            0..=500 => true,
+            _ => false,
+        };
+
+        match (prefer_lighter, candidate <= desired) {
+            (true, true) | (false, false) => (1, desired.abs_diff(candidate)),
+            _ => (2, desired.abs_diff(candidate)),
+        }
+    }
+}
+
+impl Default for FontWeight {
+    #[inline]
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+/// Slant style of a font face.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+    Oblique,
+}
+
+impl Default for FontStyle {
+    #[inline]
+    fn default() -> Self {
+        FontStyle::Normal
+    }
+}
+
+impl FontStyle {
+    /// Orders candidate styles by preference for this desired style, italic
+    /// & oblique faces standing in for one another before normal ones.
+    pub(crate) fn match_key(self, candidate: FontStyle) -> u8 {
+        match (self, candidate) {
+            (desired, candidate) if desired == candidate => 0,
+            (FontStyle::Italic, FontStyle::Oblique) | (FontStyle::Oblique, FontStyle::Italic) => 1,
+            (_, FontStyle::Normal) => 2,
+            _ => 3,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use super::{Variation, VariationFont};
+use crate::ab_glyph::{FontArc, FontRef, InvalidFont, VariableFont};
+use rustybuzz::Face;
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// The data of a font face, parsed once for text shaping.
+///
+/// Shaping needs the raw OpenType tables, which a `Font` does not have to
+/// expose, so faces are registered per [`FontId`](struct.FontId.html) with
+/// [`GlyphBrush::add_font_face`](struct.GlyphBrush.html#method.add_font_face)
+/// or [`ShapedLayout::font_face`](struct.ShapedLayout.html#method.font_face).
+///
+/// Cheap to clone, clones share the data & the parsed face.
+#[derive(Clone)]
+pub struct FontFace {
+    inner: Arc<Parsed>,
+}
+
+struct Parsed {
+    // borrow `data`, see `FontFace::new`
+    face: Face<'static>,
+    font: FontRef<'static>,
+    data: Arc<[u8]>,
+    index: u32,
+}
+
+impl FontFace {
+    /// Parses the face at `index` of the font data, `0` unless the data is a
+    /// font collection such as a `.ttc`.
+    pub fn new<D: Into<Arc<[u8]>>>(data: D, index: u32) -> Result<Self, InvalidFont> {
+        let data = data.into();
+        // SAFETY: the bytes are behind the `Arc`, so they stay at the same
+        // address when `Parsed` moves & are never mutated. `Parsed` holds the
+        // `Arc` as long as `face` & `font`, which are declared before it & so
+        // dropped first. Neither is handed out with the `'static` lifetime,
+        // `face` is borrowed from `Parsed` & fonts made from `font` hold a
+        // clone of the `Arc`, see `VariationFont`.
+        let bytes: &'static [u8] = unsafe { &*(&*data as *const [u8]) };
+        let face = Face::from_slice(bytes, index).ok_or(InvalidFont)?;
+        let font = FontRef::try_from_slice_and_index(bytes, index)?;
+
+        Ok(FontFace {
+            inner: Arc::new(Parsed {
+                face,
+                font,
+                data,
+                index,
+            }),
+        })
+    }
+
+    /// Index of the face in its font data.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.inner.index
+    }
+
+    /// Returns a font of this face for a
+    /// [`GlyphBrush`](struct.GlyphBrush.html), sharing its data.
+    pub fn font(&self) -> FontArc {
+        FontArc::new(self.instance(&[]))
+    }
+
+    /// Returns an instance of this face at variable font axis coordinates,
+    /// sharing its data. Axes left out, or missing from the font, keep their
+    /// default value.
+    pub(crate) fn instance(&self, variations: &[Variation]) -> VariationFont {
+        let mut font = self.inner.font.clone();
+        for variation in variations {
+            let _ = font.set_variation(&variation.tag, variation.value);
+        }
+        VariationFont::new(font, self.inner.data.clone())
+    }
+
+    #[inline]
+    pub(crate) fn face(&self) -> &Face<'_> {
+        &self.inner.face
+    }
+}
+
+impl fmt::Debug for FontFace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FontFace")
+            .field("index", &self.inner.index)
+            .field("data_len", &self.inner.data.len())
+            .finish()
+    }
+}
+
+/// Faces are equal when they share the same parsed data.
+impl PartialEq for FontFace {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for FontFace {}
+
+impl Hash for FontFace {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.inner) as usize).hash(state);
+    }
+}
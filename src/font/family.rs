@@ -0,0 +1,228 @@
+use super::{FontStyle, FontWeight, Synthesis};
+use glyph_brush::FontId;
+
+/// Weight from which a face counts as bold when synthesizing missing variants.
+const BOLD_THRESHOLD: u16 = 600;
+
+/// A face of a [`FontFamily`](struct.FontFamily.html).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct FontVariant {
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    pub font_id: FontId,
+}
+
+/// The face resolved for a requested weight & style, with the styling it
+/// lacks & should be synthesized.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct FontMatch {
+    pub font_id: FontId,
+    /// A bold weight was requested but only lighter faces are available.
+    pub synthetic_bold: bool,
+    /// An italic or oblique style was requested but only upright faces are
+    /// available.
+    pub synthetic_oblique: bool,
+}
+
+impl FontMatch {
+    /// Returns the styling to synthesize, see
+    /// [`GlyphBrush::add_synthetic_font`](struct.GlyphBrush.html#method.add_synthetic_font).
+    pub fn synthesis(&self) -> Synthesis {
+        Synthesis {
+            embolden: match self.synthetic_bold {
+                true => Synthesis::BOLD.embolden,
+                false => 0.0,
+            },
+            skew: match self.synthetic_oblique {
+                true => Synthesis::OBLIQUE.skew,
+                false => 0.0,
+            },
+        }
+    }
+}
+
+/// Faces of a typeface, e.g. its regular, bold, italic & bold-italic files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontFamily {
+    name: String,
+    variants: Vec<FontVariant>,
+}
+
+impl FontFamily {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn variants(&self) -> &[FontVariant] {
+        &self.variants
+    }
+
+    /// Resolves the face closest to the weight & style, matching as CSS does.
+    pub fn resolve(&self, weight: FontWeight, style: FontStyle) -> Option<FontMatch> {
+        let variant = self
+            .variants
+            .iter()
+            .min_by_key(|v| (style.match_key(v.style), weight.match_key(v.weight)))?;
+
+        Some(FontMatch {
+            font_id: variant.font_id,
+            synthetic_bold: weight.0 >= BOLD_THRESHOLD && variant.weight.0 < BOLD_THRESHOLD,
+            synthetic_oblique: style != FontStyle::Normal && variant.style == FontStyle::Normal,
+        })
+    }
+}
+
+/// Registry of font families, resolving a family, weight & style request to
+/// a [`FontId`](struct.FontId.html).
+///
+/// Filled with
+/// [`GlyphBrush::add_font_variant`](struct.GlyphBrush.html#method.add_font_variant)
+/// or [`GlyphBrushBuilder::add_font_variant`](struct.GlyphBrushBuilder.html#method.add_font_variant).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FontFamilies {
+    families: Vec<FontFamily>,
+}
+
+impl FontFamilies {
+    /// Registers an added font as a face of the family, replacing any face
+    /// previously registered for the same weight & style.
+    pub fn insert(&mut self, family: &str, weight: FontWeight, style: FontStyle, font_id: FontId) {
+        let variant = FontVariant {
+            weight,
+            style,
+            font_id,
+        };
+
+        let index = match self.families.iter().position(|f| f.name == family) {
+            Some(index) => index,
+            None => {
+                self.families.push(FontFamily {
+                    name: family.to_owned(),
+                    variants: Vec::new(),
+                });
+                self.families.len() - 1
+            }
+        };
+
+        let variants = &mut self.families[index].variants;
+        match variants
+            .iter_mut()
+            .find(|v| v.weight == weight && v.style == style)
+        {
+            Some(existing) => *existing = variant,
+            None => variants.push(variant),
+        }
+    }
+
+    /// Returns the family of the exact name.
+    pub fn get(&self, family: &str) -> Option<&FontFamily> {
+        self.families.iter().find(|f| f.name == family)
+    }
+
+    /// Returns all registered families.
+    #[inline]
+    pub fn families(&self) -> &[FontFamily] {
+        &self.families
+    }
+
+    /// Resolves the family's face closest to the weight & style, see
+    /// [`FontFamily::resolve`](struct.FontFamily.html#method.resolve).
+    pub fn resolve(&self, family: &str, weight: FontWeight, style: FontStyle) -> Option<FontMatch> {
+        self.get(family)?.resolve(weight, style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn families() -> FontFamilies {
+        let mut families = FontFamilies::default();
+        families.insert("Sans", FontWeight::LIGHT, FontStyle::Normal, FontId(0));
+        families.insert("Sans", FontWeight::NORMAL, FontStyle::Normal, FontId(1));
+        families.insert("Sans", FontWeight::BOLD, FontStyle::Normal, FontId(2));
+        families.insert("Sans", FontWeight::NORMAL, FontStyle::Italic, FontId(3));
+        families.insert("Serif", FontWeight::NORMAL, FontStyle::Normal, FontId(4));
+        families
+    }
+
+    fn resolve(families: &FontFamilies, weight: u16, style: FontStyle) -> FontId {
+        families
+            .resolve("Sans", FontWeight(weight), style)
+            .unwrap()
+            .font_id
+    }
+
+    #[test]
+    fn resolves_the_nearest_weight() {
+        let families = families();
+        assert_eq!(resolve(&families, 400, FontStyle::Normal), FontId(1));
+        // 400 to 500 look heavier up to 500 first
+        assert_eq!(resolve(&families, 450, FontStyle::Normal), FontId(1));
+        // lighter weights look lighter first
+        assert_eq!(resolve(&families, 350, FontStyle::Normal), FontId(0));
+        assert_eq!(resolve(&families, 100, FontStyle::Normal), FontId(0));
+        // bolder weights look heavier first
+        assert_eq!(resolve(&families, 600, FontStyle::Normal), FontId(2));
+        assert_eq!(resolve(&families, 900, FontStyle::Normal), FontId(2));
+    }
+
+    #[test]
+    fn resolves_the_style_before_the_weight() {
+        let families = families();
+        assert_eq!(resolve(&families, 700, FontStyle::Italic), FontId(3));
+        // italic stands in for oblique
+        assert_eq!(resolve(&families, 400, FontStyle::Oblique), FontId(3));
+
+        let serif = families
+            .resolve("Serif", FontWeight::BOLD, FontStyle::Italic)
+            .unwrap();
+        assert_eq!(serif.font_id, FontId(4));
+    }
+
+    #[test]
+    fn replaces_variants_of_the_same_weight_and_style() {
+        let mut families = families();
+        families.insert("Sans", FontWeight::BOLD, FontStyle::Normal, FontId(5));
+        assert_eq!(families.get("Sans").unwrap().variants().len(), 4);
+        assert_eq!(resolve(&families, 700, FontStyle::Normal), FontId(5));
+
+        assert_eq!(families.families().len(), 2);
+        assert!(families
+            .resolve("Mono", FontWeight::NORMAL, FontStyle::Normal)
+            .is_none());
+    }
+
+    #[test]
+    fn synthesizes_missing_styling() {
+        let families = families();
+        let matched = |family, weight, style| {
+            let matched = families.resolve(family, weight, style).unwrap();
+            (matched.synthetic_bold, matched.synthetic_oblique)
+        };
+
+        assert_eq!(
+            matched("Sans", FontWeight::BOLD, FontStyle::Italic),
+            (true, false)
+        );
+        assert_eq!(
+            matched("Sans", FontWeight::BOLD, FontStyle::Normal),
+            (false, false)
+        );
+        assert_eq!(
+            matched("Serif", FontWeight::SEMI_BOLD, FontStyle::Oblique),
+            (true, true)
+        );
+
+        let serif = families
+            .resolve("Serif", FontWeight::BLACK, FontStyle::Italic)
+            .unwrap();
+        assert_eq!(serif.synthesis(), Synthesis::BOLD_OBLIQUE);
+        let sans = families
+            .resolve("Sans", FontWeight::NORMAL, FontStyle::Normal)
+            .unwrap();
+        assert_eq!(sans.synthesis(), Synthesis::NONE);
+    }
+}
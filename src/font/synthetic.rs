@@ -0,0 +1,236 @@
+use crate::ab_glyph::{
+    point, v2, CodepointIdIter, Font, GlyphId, GlyphSvg, Outline, OutlineCurve, Point,
+};
+use std::hash::{Hash, Hasher};
+
+/// Styling synthesized for fonts lacking a bold or italic face, see
+/// [`GlyphBrush::add_synthetic_font`](struct.GlyphBrush.html#method.add_synthetic_font).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Synthesis {
+    /// Emboldening in ems, widening outlines & advances.
+    pub embolden: f32,
+    /// Horizontal shear of oblique glyphs, the tangent of their slant angle.
+    pub skew: f32,
+}
+
+impl Synthesis {
+    pub const NONE: Synthesis = Synthesis {
+        embolden: 0.0,
+        skew: 0.0,
+    };
+    /// Emboldening of 1/24 em, as FreeType does.
+    pub const BOLD: Synthesis = Synthesis {
+        embolden: 1.0 / 24.0,
+        skew: 0.0,
+    };
+    /// Slant of about 12°.
+    pub const OBLIQUE: Synthesis = Synthesis {
+        embolden: 0.0,
+        skew: 0.21,
+    };
+    pub const BOLD_OBLIQUE: Synthesis = Synthesis {
+        embolden: Self::BOLD.embolden,
+        skew: Self::OBLIQUE.skew,
+    };
+
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+}
+
+impl PartialEq for Synthesis {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.embolden.to_bits() == other.embolden.to_bits()
+            && self.skew.to_bits() == other.skew.to_bits()
+    }
+}
+
+impl Eq for Synthesis {}
+
+impl Hash for Synthesis {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.embolden.to_bits().hash(state);
+        self.skew.to_bits().hash(state);
+    }
+}
+
+/// A font emboldened by rasterizing its outlines twice, horizontally offset
+/// by the emboldening, growing advances to match.
+///
+/// Shearing is left to the vertex shader.
+#[derive(Debug, Clone)]
+pub(crate) struct SyntheticFont<F> {
+    font: F,
+    /// Emboldening in font units.
+    embolden: f32,
+}
+
+impl<F: Font> SyntheticFont<F> {
+    pub fn new(font: F, synthesis: Synthesis) -> Self {
+        let units_per_em = font.units_per_em().unwrap_or(1000.0);
+        SyntheticFont {
+            embolden: synthesis.embolden * units_per_em,
+            font,
+        }
+    }
+}
+
+impl<F: Font> Font for SyntheticFont<F> {
+    #[inline]
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    #[inline]
+    fn ascent_unscaled(&self) -> f32 {
+        self.font.ascent_unscaled()
+    }
+
+    #[inline]
+    fn descent_unscaled(&self) -> f32 {
+        self.font.descent_unscaled()
+    }
+
+    #[inline]
+    fn line_gap_unscaled(&self) -> f32 {
+        self.font.line_gap_unscaled()
+    }
+
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        self.font.italic_angle()
+    }
+
+    #[inline]
+    fn glyph_id(&self, c: char) -> GlyphId {
+        self.font.glyph_id(c)
+    }
+
+    #[inline]
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_advance_unscaled(id) + self.embolden
+    }
+
+    #[inline]
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        let mut outline = self.font.outline(id)?;
+        if self.embolden <= 0.0 {
+            return Some(outline);
+        }
+
+        // overlapping coverage of both copies saturates, drawing their union
+        let offset = point(self.embolden, 0.0);
+        let shifted: Vec<_> = outline
+            .curves
+            .iter()
+            .map(|curve| translate(curve, offset))
+            .collect();
+        outline.curves.extend(shifted);
+        outline.bounds.max.x += self.embolden;
+
+        Some(outline)
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    #[inline]
+    fn glyph_raster_image2(&self, id: GlyphId, pixel_size: u16) -> Option<v2::GlyphImage<'_>> {
+        self.font.glyph_raster_image2(id, pixel_size)
+    }
+
+    #[inline]
+    fn glyph_svg_image(&self, id: GlyphId) -> Option<GlyphSvg<'_>> {
+        self.font.glyph_svg_image(id)
+    }
+
+    #[inline]
+    fn font_data(&self) -> &[u8] {
+        self.font.font_data()
+    }
+}
+
+fn translate(curve: &OutlineCurve, offset: Point) -> OutlineCurve {
+    match *curve {
+        OutlineCurve::Line(a, b) => OutlineCurve::Line(a + offset, b + offset),
+        OutlineCurve::Quad(a, b, c) => OutlineCurve::Quad(a + offset, b + offset, c + offset),
+        OutlineCurve::Cubic(a, b, c, d) => {
+            OutlineCurve::Cubic(a + offset, b + offset, c + offset, d + offset)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ab_glyph::FontRef;
+
+    const FONT: &[u8] = include_bytes!("../../examples/Inconsolata-Regular.ttf");
+
+    #[test]
+    fn emboldens_outlines_and_advances() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let bold = SyntheticFont::new(font.clone(), Synthesis::BOLD);
+        let embolden = font.units_per_em().unwrap() / 24.0;
+        let id = font.glyph_id('l');
+
+        assert_eq!(
+            bold.h_advance_unscaled(id),
+            font.h_advance_unscaled(id) + embolden
+        );
+        assert_eq!(
+            bold.h_side_bearing_unscaled(id),
+            font.h_side_bearing_unscaled(id)
+        );
+
+        let (regular, bold) = (font.outline(id).unwrap(), bold.outline(id).unwrap());
+        assert_eq!(bold.curves.len(), 2 * regular.curves.len());
+        assert_eq!(bold.bounds.min, regular.bounds.min);
+        assert_eq!(bold.bounds.max.x, regular.bounds.max.x + embolden);
+        assert_eq!(bold.bounds.max.y, regular.bounds.max.y);
+    }
+
+    #[test]
+    fn leaves_obliques_to_the_shader() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let oblique = SyntheticFont::new(font.clone(), Synthesis::OBLIQUE);
+        let id = font.glyph_id('l');
+
+        assert_eq!(oblique.h_advance_unscaled(id), font.h_advance_unscaled(id));
+        assert_eq!(
+            oblique.outline(id).unwrap().curves.len(),
+            font.outline(id).unwrap().curves.len()
+        );
+        assert!(Synthesis::NONE.is_none());
+        assert!(!Synthesis::BOLD_OBLIQUE.is_none());
+    }
+}
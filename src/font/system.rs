@@ -0,0 +1,428 @@
+use super::{FontFace, FontStyle, FontWeight};
+use crate::ab_glyph::{FontArc, FontVec, InvalidFont};
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Directory nesting followed when scanning for fonts, guarding against
+/// symlink cycles.
+const MAX_SCAN_DEPTH: usize = 16;
+
+/// A font face found while scanning font directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemFont {
+    pub path: PathBuf,
+    /// Index of the face within a font collection file, `0` otherwise.
+    pub index: u32,
+    /// Family names of the face, typographic & legacy ones in all languages.
+    pub families: Vec<String>,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    /// Width class from `1` (ultra-condensed) to `9` (ultra-expanded), `5`
+    /// being normal.
+    pub stretch: u16,
+}
+
+impl SystemFont {
+    /// Returns true if the face is of the family, ignoring case.
+    pub fn is_family(&self, family: &str) -> bool {
+        self.families.iter().any(|f| f.eq_ignore_ascii_case(family))
+    }
+
+    /// Reads & parses the face's font file.
+    pub fn load(&self) -> Result<FontArc, LoadFontError> {
+        let data = fs::read(&self.path)?;
+        Ok(FontVec::try_from_vec_and_index(data, self.index)?.into())
+    }
+
+    /// Reads & parses the face's font file for shaping, see
+    /// [`GlyphBrush::add_font_face`](struct.GlyphBrush.html#method.add_font_face).
+    pub fn load_face(&self) -> Result<FontFace, LoadFontError> {
+        let data = fs::read(&self.path)?;
+        Ok(FontFace::new(data, self.index)?)
+    }
+}
+
+/// Font faces discovered in font directories, searchable by family name,
+/// weight & style.
+///
+/// Loaded fonts are ready for
+/// [`GlyphBrushBuilder::using_fonts`](struct.GlyphBrushBuilder.html#method.using_fonts)
+/// or [`GlyphBrush::add_font`](struct.GlyphBrush.html#method.add_font).
+///
+/// ```no_run
+/// # use luminance_glyph::{FontStyle, FontWeight, SystemFonts};
+/// # fn main() -> Result<(), luminance_glyph::LoadFontError> {
+/// let fonts = SystemFonts::scan_system();
+///
+/// let bold = fonts.load("DejaVu Sans", FontWeight::BOLD, FontStyle::Normal)?;
+/// let same = fonts.load_by_name("DejaVu Sans Bold")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SystemFonts {
+    fonts: Vec<SystemFont>,
+}
+
+impl SystemFonts {
+    /// Creates an empty set of fonts, see [`scan_dir`](#method.scan_dir).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scans the platform's font directories, e.g. the fontconfig ones on
+    /// Linux: `/usr/share/fonts`, `/usr/local/share/fonts`, `~/.fonts` &
+    /// `$XDG_DATA_HOME/fonts`.
+    pub fn scan_system() -> Self {
+        let mut fonts = Self::new();
+        for dir in system_font_dirs() {
+            fonts.scan_dir(dir);
+        }
+        fonts
+    }
+
+    /// Recursively scans a directory for `ttf`, `otf`, `ttc` & `otc` fonts,
+    /// adding their faces. Unreadable files & directories are skipped.
+    pub fn scan_dir<P: AsRef<Path>>(&mut self, dir: P) {
+        self.scan(dir.as_ref(), 0);
+    }
+
+    /// Adds the faces of a single font file.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), LoadFontError> {
+        let path = path.as_ref();
+        let data = fs::read(path)?;
+        let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+
+        let mut added = false;
+        for index in 0..count {
+            if let Ok(face) = ttf_parser::Face::parse(&data, index) {
+                self.fonts.push(describe(&face, path.to_path_buf(), index));
+                added = true;
+            }
+        }
+
+        match added {
+            true => Ok(()),
+            false => Err(InvalidFont.into()),
+        }
+    }
+
+    /// Returns all faces found.
+    #[inline]
+    pub fn fonts(&self) -> &[SystemFont] {
+        &self.fonts
+    }
+
+    /// Returns the face of the family closest to the weight & style, matching
+    /// as CSS does & preferring normal width faces. Family names are compared
+    /// ignoring case.
+    pub fn query(&self, family: &str, weight: FontWeight, style: FontStyle) -> Option<&SystemFont> {
+        self.fonts
+            .iter()
+            .filter(|font| font.is_family(family))
+            .min_by_key(|font| {
+                (
+                    style.match_key(font.style),
+                    weight.match_key(font.weight),
+                    (font.stretch as i32 - 5).abs(),
+                )
+            })
+    }
+
+    /// Loads the face of the family closest to the weight & style, see
+    /// [`query`](#method.query).
+    pub fn load(
+        &self,
+        family: &str,
+        weight: FontWeight,
+        style: FontStyle,
+    ) -> Result<FontArc, LoadFontError> {
+        self.query(family, weight, style)
+            .ok_or(LoadFontError::NotFound)?
+            .load()
+    }
+
+    /// Loads a face by full name, such as `"DejaVu Sans Bold Oblique"`: the
+    /// longest known family name followed by optional weight & style words.
+    pub fn load_by_name(&self, name: &str) -> Result<FontArc, LoadFontError> {
+        let (family, weight, style) = self.parse_name(name).ok_or(LoadFontError::NotFound)?;
+        self.load(family, weight, style)
+    }
+
+    fn parse_name<'a>(&'a self, name: &str) -> Option<(&'a str, FontWeight, FontStyle)> {
+        let name = name.trim();
+
+        self.fonts
+            .iter()
+            .flat_map(|font| &font.families)
+            .filter_map(|family| {
+                let prefix = name.get(..family.len())?;
+                let rest = &name[family.len()..];
+                let at_word_end = rest.is_empty() || rest.starts_with(char::is_whitespace);
+                if !at_word_end || !prefix.eq_ignore_ascii_case(family) {
+                    return None;
+                }
+
+                let (weight, style) = parse_variant(rest)?;
+                Some((family.as_str(), weight, style))
+            })
+            .max_by_key(|(family, ..)| family.len())
+    }
+
+    fn scan(&mut self, dir: &Path, depth: usize) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) if depth <= MAX_SCAN_DEPTH => entries,
+            _ => return,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                self.scan(&path, depth + 1);
+            } else if is_font_file(&path) {
+                let _ = self.add_file(&path);
+            }
+        }
+    }
+}
+
+/// Parses trailing weight & style words of a font name, e.g. `" Bold Italic"`.
+fn parse_variant(words: &str) -> Option<(FontWeight, FontStyle)> {
+    let mut style = FontStyle::Normal;
+    let mut weight = String::new();
+
+    for word in words.split_whitespace() {
+        match word.to_ascii_lowercase().as_str() {
+            "italic" => style = FontStyle::Italic,
+            "oblique" => style = FontStyle::Oblique,
+            _ => weight.push_str(word),
+        }
+    }
+
+    Some((FontWeight::from_name(&weight)?, style))
+}
+
+fn describe(face: &ttf_parser::Face<'_>, path: PathBuf, index: u32) -> SystemFont {
+    use ttf_parser::name_id::{FAMILY, TYPOGRAPHIC_FAMILY};
+
+    let mut families = Vec::new();
+    for name in face.names() {
+        if name.name_id == FAMILY || name.name_id == TYPOGRAPHIC_FAMILY {
+            if let Some(family) = name.to_string().filter(|f| !families.contains(f)) {
+                families.push(family);
+            }
+        }
+    }
+
+    let style = if face.is_oblique() {
+        FontStyle::Oblique
+    } else if face.is_italic() {
+        FontStyle::Italic
+    } else {
+        FontStyle::Normal
+    };
+
+    SystemFont {
+        path,
+        index,
+        families,
+        weight: FontWeight(face.weight().to_number()),
+        style,
+        stretch: face.width().to_number(),
+    }
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|font_ext| ext.eq_ignore_ascii_case(font_ext))
+        })
+}
+
+fn system_font_dirs() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let mut dirs = Vec::new();
+
+    if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        dirs.extend(home.map(|home| home.join("Library/Fonts")));
+    } else if cfg!(windows) {
+        let windir = env::var_os("WINDIR").unwrap_or_else(|| "C:\\Windows".into());
+        dirs.push(PathBuf::from(windir).join("Fonts"));
+        dirs.extend(
+            env::var_os("LOCALAPPDATA")
+                .map(|local| PathBuf::from(local).join("Microsoft\\Windows\\Fonts")),
+        );
+    } else {
+        let data_home = env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+        let data_dirs =
+            env::var("XDG_DATA_DIRS").unwrap_or_else(|_| "/usr/local/share:/usr/share".into());
+
+        dirs.extend(data_home.map(|data| data.join("fonts")));
+        dirs.extend(home.map(|home| home.join(".fonts")));
+        dirs.extend(
+            data_dirs
+                .split(':')
+                .filter(|dir| !dir.is_empty())
+                .map(|dir| Path::new(dir).join("fonts")),
+        );
+    }
+
+    dirs
+}
+
+/// Error loading a font from disk.
+#[derive(Debug)]
+pub enum LoadFontError {
+    /// No scanned face matches the request.
+    NotFound,
+    Io(io::Error),
+    InvalidFont,
+}
+
+impl fmt::Display for LoadFontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadFontError::NotFound => write!(f, "no matching font found"),
+            LoadFontError::Io(err) => write!(f, "failed to read font file: {}", err),
+            LoadFontError::InvalidFont => write!(f, "invalid font data"),
+        }
+    }
+}
+
+impl std::error::Error for LoadFontError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadFontError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadFontError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        LoadFontError::Io(err)
+    }
+}
+
+impl From<InvalidFont> for LoadFontError {
+    #[inline]
+    fn from(_: InvalidFont) -> Self {
+        LoadFontError::InvalidFont
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ab_glyph::Font;
+
+    const FIXTURE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/examples/Inconsolata-Regular.ttf"
+    );
+
+    /// Returns a fresh directory of fixture fonts, nested & among files that
+    /// aren't fonts.
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("luminance-glyph-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("truetype/inconsolata")).unwrap();
+        let _ = fs::copy(FIXTURE, dir.join("truetype/inconsolata/Inconsolata.TTF")).unwrap();
+        fs::write(dir.join("fonts.conf"), "<fontconfig/>").unwrap();
+        fs::write(dir.join("truetype/broken.ttf"), b"not a font").unwrap();
+        dir
+    }
+
+    #[test]
+    fn scans_fixture_dir() {
+        let dir = fixture_dir("scan");
+        let mut fonts = SystemFonts::new();
+        fonts.scan_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(fonts.fonts().len(), 1);
+        let font = &fonts.fonts()[0];
+        assert!(font.is_family("inconsolata"));
+        assert_eq!(font.index, 0);
+        assert_eq!(font.weight, FontWeight::NORMAL);
+        assert_eq!(font.style, FontStyle::Normal);
+        assert_eq!(font.stretch, 5);
+    }
+
+    #[test]
+    fn loads_by_family_weight_style() {
+        let mut fonts = SystemFonts::new();
+        fonts.add_file(FIXTURE).unwrap();
+
+        let font = fonts
+            .load("Inconsolata", FontWeight::NORMAL, FontStyle::Normal)
+            .unwrap();
+        assert_ne!(font.glyph_id('a').0, 0);
+
+        // the closest face stands in for missing weights & styles
+        let bold = fonts.query("INCONSOLATA", FontWeight::BOLD, FontStyle::Italic);
+        assert_eq!(bold, fonts.fonts().first());
+
+        assert!(matches!(
+            fonts.load("DejaVu Sans", FontWeight::NORMAL, FontStyle::Normal),
+            Err(LoadFontError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn loads_by_name() {
+        let mut fonts = SystemFonts::new();
+        fonts.add_file(FIXTURE).unwrap();
+
+        assert!(fonts.load_by_name("Inconsolata").is_ok());
+        assert!(fonts.load_by_name(" inconsolata Bold Italic ").is_ok());
+        assert!(matches!(
+            fonts.load_by_name("Inconsolatas"),
+            Err(LoadFontError::NotFound)
+        ));
+        assert!(matches!(
+            fonts.load_by_name("Inconsolata Wide"),
+            Err(LoadFontError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn parses_variant_words() {
+        assert_eq!(
+            parse_variant(""),
+            Some((FontWeight::NORMAL, FontStyle::Normal))
+        );
+        assert_eq!(
+            parse_variant(" Semi Bold Italic"),
+            Some((FontWeight::SEMI_BOLD, FontStyle::Italic))
+        );
+        assert_eq!(
+            parse_variant(" oblique Black"),
+            Some((FontWeight::BLACK, FontStyle::Oblique))
+        );
+        assert_eq!(parse_variant(" Condensed"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        let dir = fixture_dir("invalid");
+        let mut fonts = SystemFonts::new();
+        let broken = fonts.add_file(dir.join("truetype/broken.ttf"));
+        let missing = fonts.add_file(dir.join("missing.ttf"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(broken, Err(LoadFontError::InvalidFont)));
+        assert!(matches!(missing, Err(LoadFontError::Io(_))));
+        assert!(fonts.fonts().is_empty());
+    }
+}
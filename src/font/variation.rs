@@ -0,0 +1,232 @@
+use crate::ab_glyph::{v2, CodepointIdIter, Font, FontRef, GlyphId, GlyphSvg, Outline};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+/// A coordinate on a variable font axis, e.g. a `wght` of `650`.
+#[derive(Debug, Clone, Copy)]
+pub struct Variation {
+    /// Axis tag, such as `*b"wght"`.
+    pub tag: [u8; 4],
+    pub value: f32,
+}
+
+impl Variation {
+    #[inline]
+    pub const fn new(tag: [u8; 4], value: f32) -> Self {
+        Variation { tag, value }
+    }
+
+    /// Weight axis `wght`, from `1` to `1000`.
+    #[inline]
+    pub const fn weight(value: f32) -> Self {
+        Self::new(*b"wght", value)
+    }
+
+    /// Width axis `wdth`, a percentage of the normal width.
+    #[inline]
+    pub const fn width(value: f32) -> Self {
+        Self::new(*b"wdth", value)
+    }
+
+    /// Slant axis `slnt`, in counter-clockwise degrees.
+    #[inline]
+    pub const fn slant(value: f32) -> Self {
+        Self::new(*b"slnt", value)
+    }
+
+    /// Optical size axis `opsz`, in points.
+    #[inline]
+    pub const fn optical_size(value: f32) -> Self {
+        Self::new(*b"opsz", value)
+    }
+}
+
+impl PartialEq for Variation {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag && self.value.to_bits() == other.value.to_bits()
+    }
+}
+
+impl Eq for Variation {}
+
+impl Hash for Variation {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+        self.value.to_bits().hash(state);
+    }
+}
+
+/// An instance of a variable font at axis coordinates, sharing the font data
+/// with the other instances of the font, made by
+/// [`FontFace`](struct.FontFace.html).
+pub(crate) struct VariationFont {
+    // borrows `data`, so declared first to be dropped first
+    font: FontRef<'static>,
+    data: Arc<[u8]>,
+}
+
+impl VariationFont {
+    /// SAFETY: `font` must be parsed from `data`, which is then held as long
+    /// as `font`, keeping the borrowed bytes alive & at the same address. The
+    /// `'static` lifetime never leaves this type, `Font` methods only lend
+    /// out borrows of `self`.
+    pub(super) fn new(font: FontRef<'static>, data: Arc<[u8]>) -> Self {
+        VariationFont { font, data }
+    }
+}
+
+impl fmt::Debug for VariationFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VariationFont")
+            .field("font", &self.font)
+            .field("data_len", &self.data.len())
+            .finish()
+    }
+}
+
+impl Font for VariationFont {
+    #[inline]
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    #[inline]
+    fn ascent_unscaled(&self) -> f32 {
+        self.font.ascent_unscaled()
+    }
+
+    #[inline]
+    fn descent_unscaled(&self) -> f32 {
+        self.font.descent_unscaled()
+    }
+
+    #[inline]
+    fn line_gap_unscaled(&self) -> f32 {
+        self.font.line_gap_unscaled()
+    }
+
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        self.font.italic_angle()
+    }
+
+    #[inline]
+    fn glyph_id(&self, c: char) -> GlyphId {
+        self.font.glyph_id(c)
+    }
+
+    #[inline]
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        self.font.outline(id)
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    #[inline]
+    fn glyph_raster_image2(&self, id: GlyphId, pixel_size: u16) -> Option<v2::GlyphImage<'_>> {
+        self.font.glyph_raster_image2(id, pixel_size)
+    }
+
+    #[inline]
+    fn glyph_svg_image(&self, id: GlyphId) -> Option<GlyphSvg<'_>> {
+        self.font.glyph_svg_image(id)
+    }
+
+    #[inline]
+    fn font_data(&self) -> &[u8] {
+        self.font.font_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ab_glyph::Font, FontFace, Variation};
+
+    const FONT: &[u8] = include_bytes!("../../examples/Inconsolata-Regular.ttf");
+
+    /// Returns a font collection of an empty face followed by the font.
+    fn collection() -> Vec<u8> {
+        const FONT_OFFSET: u32 = 32;
+        let mut data = Vec::new();
+        data.extend_from_slice(b"ttcf");
+        data.extend_from_slice(&0x0001_0000_u32.to_be_bytes());
+        data.extend_from_slice(&2_u32.to_be_bytes());
+        data.extend_from_slice(&20_u32.to_be_bytes());
+        data.extend_from_slice(&FONT_OFFSET.to_be_bytes());
+        // a table directory without tables
+        data.extend_from_slice(&0x0001_0000_u32.to_be_bytes());
+        data.extend_from_slice(&[0; 8]);
+
+        let mut font = FONT.to_vec();
+        let tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+        for record in 0..tables {
+            let at = 12 + record * 16 + 8;
+            let offset = u32::from_be_bytes([font[at], font[at + 1], font[at + 2], font[at + 3]]);
+            font[at..at + 4].copy_from_slice(&(offset + FONT_OFFSET).to_be_bytes());
+        }
+        data.extend_from_slice(&font);
+        data
+    }
+
+    #[test]
+    fn instances_use_collection_index() {
+        let data = collection();
+        assert!(FontFace::new(data.clone(), 0).is_err());
+        assert!(FontFace::new(data.clone(), 2).is_err());
+
+        let face = FontFace::new(data, 1).unwrap();
+        assert_eq!(face.index(), 1);
+        let font = face.instance(&[Variation::weight(700.0)]);
+        assert_ne!(font.glyph_id('a').0, 0);
+        assert_eq!(font.glyph_count(), face.font().glyph_count());
+    }
+
+    #[test]
+    fn instances_share_face_data() {
+        let face = FontFace::new(FONT, 0).unwrap();
+        let regular = face.instance(&[]);
+        let bold = face.instance(&[Variation::weight(700.0)]);
+        drop(face);
+
+        assert_eq!(regular.font_data().as_ptr(), bold.font_data().as_ptr());
+        assert_eq!(regular.font_data(), FONT);
+    }
+}
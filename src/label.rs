@@ -0,0 +1,338 @@
+use crate::ab_glyph::{point, Rect};
+use glyph_brush::Section;
+use std::collections::HashMap;
+
+/// Size in pixels of the grid cells bucketing placed labels.
+const CELL_SIZE: f32 = 64.0;
+
+/// A section labelling a 3D world position, placed by
+/// [`GlyphBrush::queue_labels`](struct.GlyphBrush.html#method.queue_labels)
+/// next to its projected anchor if it does not overlap labels of higher
+/// priority.
+///
+/// The section's `screen_position` is replaced by the placement.
+///
+/// ```
+/// # use luminance_glyph::{Label, Section, Text};
+/// let label = Label::new(Section::default().add_text(Text::new("Café")), [2.0, 0.0, 5.0])
+///     .priority(3.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Label<'a> {
+    /// Text of the label.
+    pub section: Section<'a>,
+    /// World position the label is placed next to.
+    pub anchor: [f32; 3],
+    /// Labels of higher priority are placed first, ties in queued order.
+    pub priority: f32,
+}
+
+impl<'a> Label<'a> {
+    /// Returns a label of `section` at `anchor` with priority `0`.
+    #[inline]
+    pub fn new(section: Section<'a>, anchor: [f32; 3]) -> Self {
+        Label {
+            section,
+            anchor,
+            priority: 0.0,
+        }
+    }
+
+    /// Returns an identical `Label` with a new priority.
+    #[inline]
+    pub fn priority(self, priority: f32) -> Self {
+        Label { priority, ..self }
+    }
+}
+
+/// Where a label is placed relative to its projected anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LabelPosition {
+    /// Centered on the anchor.
+    Center,
+    /// Centered above the anchor.
+    Above,
+    /// Centered below the anchor.
+    Below,
+    /// Vertically centered left of the anchor.
+    Left,
+    /// Vertically centered right of the anchor.
+    Right,
+    /// Above the anchor, ending left of it.
+    AboveLeft,
+    /// Above the anchor, starting right of it.
+    AboveRight,
+    /// Below the anchor, ending left of it.
+    BelowLeft,
+    /// Below the anchor, starting right of it.
+    BelowRight,
+}
+
+impl LabelPosition {
+    /// Returns the top left of a `width` x `height` label placed `offset`
+    /// pixels away from the anchor.
+    fn top_left(self, [x, y]: [f32; 2], (width, height): (f32, f32), offset: f32) -> [f32; 2] {
+        let left = match self {
+            LabelPosition::Center | LabelPosition::Above | LabelPosition::Below => x - width / 2.0,
+            LabelPosition::Left | LabelPosition::AboveLeft | LabelPosition::BelowLeft => {
+                x - offset - width
+            }
+            LabelPosition::Right | LabelPosition::AboveRight | LabelPosition::BelowRight => {
+                x + offset
+            }
+        };
+        let top = match self {
+            LabelPosition::Center | LabelPosition::Left | LabelPosition::Right => y - height / 2.0,
+            LabelPosition::Above | LabelPosition::AboveLeft | LabelPosition::AboveRight => {
+                y - offset - height
+            }
+            LabelPosition::Below | LabelPosition::BelowLeft | LabelPosition::BelowRight => {
+                y + offset
+            }
+        };
+        [left, top]
+    }
+}
+
+/// Settings of label placement, see
+/// [`GlyphBrush::queue_labels`](struct.GlyphBrush.html#method.queue_labels).
+///
+/// Each label is tried at its candidate positions in order, placed at the
+/// first not overlapping a label already placed, or dropped.
+///
+/// ```
+/// # use luminance_glyph::{LabelPlacer, LabelPosition};
+/// let placer = LabelPlacer::default()
+///     .positions(vec![LabelPosition::Above, LabelPosition::Right])
+///     .padding(4.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelPlacer {
+    /// Candidate positions, tried in order.
+    pub positions: Vec<LabelPosition>,
+    /// Distance in pixels between anchors and labels placed beside them.
+    pub offset: f32,
+    /// Minimum distance in pixels between placed labels.
+    pub padding: f32,
+}
+
+impl Default for LabelPlacer {
+    #[inline]
+    fn default() -> Self {
+        LabelPlacer {
+            positions: vec![
+                LabelPosition::Right,
+                LabelPosition::Left,
+                LabelPosition::Above,
+                LabelPosition::Below,
+            ],
+            offset: 4.0,
+            padding: 2.0,
+        }
+    }
+}
+
+impl LabelPlacer {
+    /// Returns an identical `LabelPlacer` with new candidate positions.
+    #[inline]
+    pub fn positions(self, positions: Vec<LabelPosition>) -> Self {
+        LabelPlacer { positions, ..self }
+    }
+
+    /// Returns an identical `LabelPlacer` with a new anchor offset in pixels.
+    #[inline]
+    pub fn offset(self, offset: f32) -> Self {
+        LabelPlacer { offset, ..self }
+    }
+
+    /// Returns an identical `LabelPlacer` with a new padding in pixels.
+    #[inline]
+    pub fn padding(self, padding: f32) -> Self {
+        LabelPlacer { padding, ..self }
+    }
+
+    /// Returns the first candidate rect of a label measured as `bounds`
+    /// not overlapping the placed ones.
+    pub(crate) fn place(&self, placed: &Occupancy, anchor: [f32; 2], bounds: Rect) -> Option<Rect> {
+        let size = (bounds.width(), bounds.height());
+        self.positions.iter().find_map(|position| {
+            let [left, top] = position.top_left(anchor, size, self.offset);
+            let rect = Rect {
+                min: point(left, top),
+                max: point(left + size.0, top + size.1),
+            };
+            (!placed.overlaps(rect, self.padding)).then_some(rect)
+        })
+    }
+}
+
+/// Returns the pixel position of a world position projected by a
+/// column-major view-projection matrix, `None` if outside the view volume.
+pub(crate) fn project(
+    view_projection: &[f32; 16],
+    [x, y, z]: [f32; 3],
+    (width, height): (f32, f32),
+) -> Option<[f32; 2]> {
+    let m = view_projection;
+    let clip = |row: usize| m[row] * x + m[4 + row] * y + m[8 + row] * z + m[12 + row];
+    let w = clip(3);
+    if w <= 0.0 {
+        return None;
+    }
+
+    let ndc = [clip(0) / w, clip(1) / w, clip(2) / w];
+    if ndc.iter().any(|c| c.abs() > 1.0) {
+        return None;
+    }
+    Some([(ndc[0] + 1.0) / 2.0 * width, (1.0 - ndc[1]) / 2.0 * height])
+}
+
+/// Rects of placed labels, bucketed in a uniform grid to find overlaps.
+#[derive(Debug, Default)]
+pub(crate) struct Occupancy {
+    rects: Vec<Rect>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl Occupancy {
+    /// Returns whether a rect grown by `padding` overlaps a placed one.
+    pub fn overlaps(&self, rect: Rect, padding: f32) -> bool {
+        let padded = Rect {
+            min: point(rect.min.x - padding, rect.min.y - padding),
+            max: point(rect.max.x + padding, rect.max.y + padding),
+        };
+        self.cells_of(padded).any(|cell| {
+            self.cells.get(&cell).is_some_and(|indices| {
+                indices.iter().any(|&index| {
+                    let other = self.rects[index];
+                    padded.min.x < other.max.x
+                        && other.min.x < padded.max.x
+                        && padded.min.y < other.max.y
+                        && other.min.y < padded.max.y
+                })
+            })
+        })
+    }
+
+    pub fn insert(&mut self, rect: Rect) {
+        let index = self.rects.len();
+        self.rects.push(rect);
+        let cells: Vec<_> = self.cells_of(rect).collect();
+        for cell in cells {
+            self.cells.entry(cell).or_default().push(index);
+        }
+    }
+
+    fn cells_of(&self, rect: Rect) -> impl Iterator<Item = (i32, i32)> {
+        let cell = |v: f32| (v / CELL_SIZE).floor() as i32;
+        let (x0, x1) = (cell(rect.min.x), cell(rect.max.x));
+        let (y0, y1) = (cell(rect.min.y), cell(rect.max.y));
+        (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rustfmt::skip]
+    const IDENTITY: [f32; 16] = [
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ];
+
+    fn rect(min: (f32, f32), max: (f32, f32)) -> Rect {
+        Rect {
+            min: point(min.0, min.1),
+            max: point(max.0, max.1),
+        }
+    }
+
+    #[test]
+    fn projects_to_pixels() {
+        let size = (800.0, 600.0);
+        assert_eq!(
+            project(&IDENTITY, [0.0, 0.0, 0.0], size),
+            Some([400.0, 300.0])
+        );
+        // y points up in clip space, down on screen
+        assert_eq!(project(&IDENTITY, [-1.0, 1.0, 0.0], size), Some([0.0, 0.0]));
+        assert_eq!(
+            project(&IDENTITY, [0.5, -0.5, 0.5], size),
+            Some([600.0, 450.0])
+        );
+    }
+
+    #[test]
+    fn divides_by_w() {
+        // w = 2 halves the clip coordinates
+        let mut view_projection = IDENTITY;
+        view_projection[15] = 2.0;
+        assert_eq!(
+            project(&view_projection, [1.0, 1.0, 0.0], (800.0, 600.0)),
+            Some([600.0, 150.0])
+        );
+    }
+
+    #[test]
+    fn culls_outside_the_view_volume() {
+        let size = (800.0, 600.0);
+        assert_eq!(project(&IDENTITY, [1.5, 0.0, 0.0], size), None);
+        assert_eq!(project(&IDENTITY, [0.0, 0.0, -1.5], size), None);
+
+        // behind the camera
+        let mut behind = IDENTITY;
+        behind[15] = -1.0;
+        assert_eq!(project(&behind, [0.0, 0.0, 0.0], size), None);
+    }
+
+    #[test]
+    fn finds_overlaps_across_cells() {
+        let mut occupancy = Occupancy::default();
+        occupancy.insert(rect((50.0, 50.0), (150.0, 80.0)));
+
+        // in other cells than the rect's first one
+        assert!(occupancy.overlaps(rect((140.0, 70.0), (200.0, 90.0)), 0.0));
+        assert!(!occupancy.overlaps(rect((151.0, 50.0), (200.0, 80.0)), 0.0));
+        // touching edges don't overlap
+        assert!(!occupancy.overlaps(rect((150.0, 50.0), (200.0, 80.0)), 0.0));
+        assert!(!occupancy.overlaps(rect((-100.0, -100.0), (-50.0, -50.0)), 0.0));
+    }
+
+    #[test]
+    fn pads_overlaps() {
+        let mut occupancy = Occupancy::default();
+        occupancy.insert(rect((0.0, 0.0), (10.0, 10.0)));
+
+        let beside = rect((13.0, 0.0), (20.0, 10.0));
+        assert!(!occupancy.overlaps(beside, 2.0));
+        assert!(occupancy.overlaps(beside, 4.0));
+    }
+
+    #[test]
+    fn places_at_first_free_position() {
+        let placer = LabelPlacer::default().offset(10.0);
+        let bounds = rect((0.0, 0.0), (40.0, 10.0));
+        let mut occupancy = Occupancy::default();
+
+        let right = placer.place(&occupancy, [100.0, 100.0], bounds).unwrap();
+        assert_eq!(right, rect((110.0, 95.0), (150.0, 105.0)));
+        occupancy.insert(right);
+
+        let left = placer.place(&occupancy, [100.0, 100.0], bounds).unwrap();
+        assert_eq!(left, rect((50.0, 95.0), (90.0, 105.0)));
+        occupancy.insert(left);
+
+        let above = placer.place(&occupancy, [100.0, 100.0], bounds).unwrap();
+        assert_eq!(above, rect((80.0, 80.0), (120.0, 90.0)));
+        occupancy.insert(above);
+
+        let below = placer.place(&occupancy, [100.0, 100.0], bounds).unwrap();
+        assert_eq!(below, rect((80.0, 110.0), (120.0, 120.0)));
+        occupancy.insert(below);
+
+        assert_eq!(placer.place(&occupancy, [100.0, 100.0], bounds), None);
+    }
+}
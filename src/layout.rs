@@ -0,0 +1,19 @@
+mod bidi;
+mod faces;
+mod fallback;
+mod lines;
+mod overflow;
+mod rotate;
+mod shaped;
+mod shaper;
+mod spacing;
+mod vertical;
+mod words;
+
+pub use bidi::{BidiLine, BidiMap, TextDirection};
+pub(crate) use faces::Faces;
+pub use overflow::Overflow;
+pub(crate) use rotate::rotated_about_center;
+pub use shaped::ShapedLayout;
+pub use spacing::Spacing;
+pub use vertical::VerticalLayout;
@@ -0,0 +1,143 @@
+use super::{lines::visual_order, lines::Line};
+use std::ops::Range;
+use unicode_bidi::{BidiClass, BidiInfo, Level};
+
+/// Base direction of the paragraphs of a section, per the Unicode
+/// Bidirectional Algorithm.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Direction taken from the first strong character of each paragraph,
+    /// left-to-right if there is none.
+    Auto,
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Default for TextDirection {
+    #[inline]
+    fn default() -> Self {
+        TextDirection::Auto
+    }
+}
+
+impl TextDirection {
+    #[inline]
+    pub(crate) fn level(self) -> Option<Level> {
+        match self {
+            TextDirection::Auto => None,
+            TextDirection::LeftToRight => Some(Level::ltr()),
+            TextDirection::RightToLeft => Some(Level::rtl()),
+        }
+    }
+}
+
+/// Logical to visual character order of laid out lines, for use in cursor
+/// placement & hit-testing.
+///
+/// Obtained from [`ShapedLayout::bidi_map`](struct.ShapedLayout.html#method.bidi_map).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BidiMap {
+    pub lines: Vec<BidiLine>,
+}
+
+/// Character order of a single line of a [`BidiMap`](struct.BidiMap.html).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BidiLine {
+    /// Paragraph embedding level, odd if the line is right-to-left.
+    pub paragraph_level: u8,
+    /// `(section_index, byte_index)` of each character, in logical order.
+    pub chars: Vec<(usize, usize)>,
+    /// Embedding level of each character in logical order, odd levels are
+    /// right-to-left.
+    pub levels: Vec<u8>,
+    /// Visual index, from the left, of each character in logical order.
+    pub logical_to_visual: Vec<usize>,
+    /// Logical index of each character in visual order, from the left.
+    pub visual_to_logical: Vec<usize>,
+}
+
+impl BidiLine {
+    /// Returns true if the character at the logical index is right-to-left.
+    #[inline]
+    pub fn is_rtl(&self, logical_index: usize) -> bool {
+        self.levels[logical_index] % 2 == 1
+    }
+
+    pub(crate) fn new(bidi: &BidiInfo<'_>, range: Range<usize>, section_ends: &[usize]) -> Self {
+        let paragraph_level = paragraph_level(bidi, range.start);
+        let byte_levels = line_levels(bidi, paragraph_level, range.clone());
+
+        let mut chars = Vec::new();
+        let mut levels = Vec::new();
+        for (index, _) in bidi.text[range.clone()].char_indices() {
+            let offset = range.start + index;
+            let section_index = section_ends.partition_point(|end| *end <= offset);
+            let section_start = section_index
+                .checked_sub(1)
+                .map_or(0, |index| section_ends[index]);
+
+            chars.push((section_index, offset - section_start));
+            levels.push(byte_levels[index].number());
+        }
+
+        let visual_to_logical = visual_order(&levels);
+        let mut logical_to_visual = vec![0; visual_to_logical.len()];
+        for (visual, logical) in visual_to_logical.iter().enumerate() {
+            logical_to_visual[*logical] = visual;
+        }
+
+        BidiLine {
+            paragraph_level: paragraph_level.number(),
+            chars,
+            levels,
+            logical_to_visual,
+            visual_to_logical,
+        }
+    }
+}
+
+/// Resolves the final embedding levels of a laid out line's glyphs.
+pub(crate) fn reorder_line(bidi: &BidiInfo<'_>, line: &mut Line) {
+    let paragraph_level = paragraph_level(bidi, line.range.start);
+    let levels = line_levels(bidi, paragraph_level, line.range.clone());
+
+    line.level = paragraph_level.number();
+    for glyph in &mut line.glyphs {
+        glyph.level = levels[glyph.offset - line.range.start].number();
+    }
+}
+
+fn paragraph_level(bidi: &BidiInfo<'_>, offset: usize) -> Level {
+    bidi.paragraphs
+        .iter()
+        .find(|para| para.range.contains(&offset))
+        .or_else(|| bidi.paragraphs.last())
+        .map_or_else(Level::ltr, |para| para.level)
+}
+
+/// Returns per byte levels of a line, with separators & trailing whitespace
+/// reset to the paragraph level (rule L1).
+fn line_levels(bidi: &BidiInfo<'_>, paragraph_level: Level, range: Range<usize>) -> Vec<Level> {
+    use BidiClass::*;
+
+    let mut levels = bidi.levels[range.clone()].to_vec();
+    let classes = &bidi.original_classes[range];
+
+    let mut trailing = true;
+    for (level, class) in levels.iter_mut().zip(classes).rev() {
+        match class {
+            B | S => {
+                *level = paragraph_level;
+                trailing = true;
+            }
+            WS | FSI | LRI | RLI | PDI | BN | LRE | RLE | LRO | RLO | PDF => {
+                if trailing {
+                    *level = paragraph_level;
+                }
+            }
+            _ => trailing = false,
+        }
+    }
+
+    levels
+}
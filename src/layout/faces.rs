@@ -0,0 +1,67 @@
+use crate::{FontFace, Variation};
+use glyph_brush::FontId;
+use rustybuzz::{ttf_parser::Tag, Face};
+use std::borrow::Cow;
+
+/// Font faces to shape with & their variation coordinates, by font.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub(crate) struct Faces {
+    pub faces: Vec<(FontId, FontFace)>,
+    pub variations: Vec<(FontId, Vec<Variation>)>,
+}
+
+impl Faces {
+    pub fn insert(&mut self, font_id: FontId, face: FontFace) {
+        self.faces.retain(|(id, _)| *id != font_id);
+        self.faces.push((font_id, face));
+    }
+
+    pub fn insert_variations(&mut self, font_id: FontId, variations: Vec<Variation>) {
+        self.variations.retain(|(id, _)| *id != font_id);
+        self.variations.push((font_id, variations));
+    }
+
+    #[inline]
+    pub fn get(&self, font_id: FontId) -> Option<&FontFace> {
+        self.faces
+            .iter()
+            .find(|(id, _)| *id == font_id)
+            .map(|(_, face)| face)
+    }
+
+    /// Adds the faces & variation coordinates of fonts missing them.
+    pub fn extend(&mut self, other: &Faces) {
+        for (font_id, face) in &other.faces {
+            if !self.faces.iter().any(|(id, _)| id == font_id) {
+                self.faces.push((*font_id, face.clone()));
+            }
+        }
+        for (font_id, variations) in &other.variations {
+            if !self.variations.iter().any(|(id, _)| id == font_id) {
+                self.variations.push((*font_id, variations.clone()));
+            }
+        }
+    }
+
+    /// Returns the parsed face of the font at its variation coordinates, if
+    /// it has a face. Faces are only copied to set coordinates.
+    pub fn face(&self, font_id: FontId) -> Option<Cow<'_, Face<'_>>> {
+        let face = self.get(font_id)?.face();
+
+        match self.variations.iter().find(|(id, _)| *id == font_id) {
+            Some((_, variations)) => {
+                let variations: Vec<_> = variations
+                    .iter()
+                    .map(|v| rustybuzz::Variation {
+                        tag: Tag::from_bytes(&v.tag),
+                        value: v.value,
+                    })
+                    .collect();
+                let mut face = face.clone();
+                face.set_variations(&variations);
+                Some(Cow::Owned(face))
+            }
+            None => Some(Cow::Borrowed(face)),
+        }
+    }
+}
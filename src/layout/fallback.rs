@@ -0,0 +1,100 @@
+use crate::ab_glyph::{Font, GlyphId};
+use glyph_brush::FontId;
+
+/// Fonts to fall back to, in order, for characters missing from a section's
+/// font.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub(crate) struct Fallback {
+    pub fonts: Vec<FontId>,
+    /// Per section overrides of `fonts`, by section index.
+    pub sections: Vec<(usize, Vec<FontId>)>,
+}
+
+impl Fallback {
+    /// Returns the fallback chain of a section, starting with its own font.
+    pub fn chain(&self, section_index: usize, font_id: FontId) -> Vec<FontId> {
+        let fallback = self
+            .sections
+            .iter()
+            .find(|(index, _)| *index == section_index)
+            .map_or(&self.fonts, |(_, fonts)| fonts);
+
+        let mut chain = vec![font_id];
+        chain.extend(fallback.iter().filter(|id| **id != font_id));
+        chain
+    }
+}
+
+/// Picks the font of the chain to render `c` with.
+///
+/// Characters without a script of their own, such as spaces & combining
+/// marks, stay in the `current` font when it has them so runs aren't split
+/// needlessly. Characters no font has use the first font of the chain.
+pub(crate) fn select_font<F: Font>(
+    fonts: &[F],
+    chain: &[FontId],
+    c: char,
+    script_neutral: bool,
+    current: Option<FontId>,
+) -> FontId {
+    let has_glyph = |font_id: FontId| fonts[font_id].glyph_id(c) != GlyphId(0);
+
+    if let Some(current) = current.filter(|id| script_neutral && has_glyph(*id)) {
+        return current;
+    }
+
+    chain
+        .iter()
+        .copied()
+        .find(|id| has_glyph(*id))
+        .unwrap_or(chain[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ab_glyph::FontRef;
+
+    const FONT: &[u8] = include_bytes!("../../examples/Inconsolata-Regular.ttf");
+
+    #[test]
+    fn chains_section_fonts_before_fallbacks() {
+        let fallback = Fallback {
+            fonts: vec![FontId(1), FontId(2)],
+            sections: vec![(1, vec![FontId(3)])],
+        };
+        assert_eq!(
+            fallback.chain(0, FontId(0)),
+            [FontId(0), FontId(1), FontId(2)]
+        );
+        // the section font isn't repeated
+        assert_eq!(fallback.chain(0, FontId(2)), [FontId(2), FontId(1)]);
+        // per section overrides
+        assert_eq!(fallback.chain(1, FontId(0)), [FontId(0), FontId(3)]);
+    }
+
+    #[test]
+    fn selects_the_first_font_having_the_char() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let fonts = [font.clone(), font];
+        let chain = [FontId(1), FontId(0)];
+
+        assert_eq!(select_font(&fonts, &chain, 'a', false, None), FontId(1));
+        // no font has it
+        assert_eq!(select_font(&fonts, &chain, '字', false, None), FontId(1));
+        // script neutral characters stay in the current font
+        assert_eq!(
+            select_font(&fonts, &chain, ' ', true, Some(FontId(0))),
+            FontId(0)
+        );
+        assert_eq!(
+            select_font(&fonts, &chain, 'a', false, Some(FontId(0))),
+            FontId(1)
+        );
+        // unless it lacks them
+        assert_eq!(
+            select_font(&fonts, &chain, '字', true, Some(FontId(0))),
+            FontId(1)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use super::{
+    shaper::ShapedGlyph,
+    words::{VMetrics, Word},
+};
+use crate::ab_glyph::point;
+use glyph_brush::{HorizontalAlign, SectionGlyph};
+use std::ops::Range;
+
+#[derive(Default)]
+pub(crate) struct Line {
+    pub glyphs: Vec<ShapedGlyph>,
+    pub max_v_metrics: VMetrics,
+    /// Width of the line, excluding trailing whitespace.
+    pub rightmost: f32,
+    /// Width of the line, including trailing whitespace.
+    pub width: f32,
+    /// Byte range of the line in the concatenated text.
+    pub range: Range<usize>,
+    /// Paragraph embedding level, odd if the line is right-to-left.
+    pub level: u8,
+    /// The line ends with a hard break, or the text.
+    pub paragraph_end: bool,
+}
+
+impl Line {
+    #[inline]
+    pub(crate) fn line_height(&self) -> f32 {
+        self.max_v_metrics.height()
+    }
+
+    fn push_hyphen(&mut self, hyphen: ShapedGlyph) {
+        self.rightmost += hyphen.advance;
+        self.width += hyphen.advance;
+        self.glyphs.push(hyphen);
+    }
+
+    /// Stretches whitespace between words so that the line fills `width`.
+    pub fn justify(&mut self, width: f32) {
+        let stretch = width - self.rightmost;
+        let content_end = self
+            .glyphs
+            .iter()
+            .rposition(|g| !g.whitespace)
+            .map_or(0, |index| index + 1);
+        let is_gap = |g: &ShapedGlyph| g.whitespace && !g.control;
+
+        let gaps = self.glyphs[..content_end]
+            .iter()
+            .filter(|g| is_gap(g))
+            .count();
+        if gaps == 0 || stretch <= 0.0 {
+            return;
+        }
+
+        let gap_stretch = stretch / gaps as f32;
+        for glyph in self.glyphs[..content_end].iter_mut().filter(|g| is_gap(g)) {
+            glyph.advance += gap_stretch;
+        }
+        self.rightmost = width;
+        self.width += stretch;
+    }
+
+    /// Positions the line's glyphs in visual order with the top of the line
+    /// at `screen_y`.
+    pub fn aligned_on_screen(
+        self,
+        (screen_x, screen_y): (f32, f32),
+        h_align: HorizontalAlign,
+    ) -> Vec<SectionGlyph> {
+        // - Right alignment attained from left by shifting the line
+        //   leftwards by the rightmost x distance from render position
+        // - Central alignment is attained from left by shifting the line
+        //   leftwards by half the rightmost x distance from render position
+        let left = match h_align {
+            HorizontalAlign::Left => screen_x,
+            HorizontalAlign::Center => screen_x - self.rightmost / 2.0,
+            HorizontalAlign::Right => screen_x - self.rightmost,
+        };
+        let baseline = screen_y + self.max_v_metrics.ascent;
+
+        // trailing whitespace of right-to-left lines lies visually leftmost
+        let left = match self.level % 2 {
+            1 => left - (self.width - self.rightmost),
+            _ => left,
+        };
+
+        let levels: Vec<_> = self.glyphs.iter().map(|g| g.level).collect();
+        let mut glyphs: Vec<_> = self.glyphs.into_iter().map(Some).collect();
+
+        let mut caret = left;
+        let mut out = Vec::with_capacity(glyphs.len());
+        for index in visual_order(&levels) {
+            let ShapedGlyph {
+                mut glyph,
+                advance,
+                control,
+                ..
+            } = glyphs[index].take().unwrap();
+
+            glyph.glyph.position += point(caret, baseline);
+            caret += advance;
+
+            if !control {
+                out.push(glyph);
+            }
+        }
+
+        out
+    }
+}
+
+/// Lays words out into lines no wider than `width_bound`, lines wrapped
+/// after a hyphenated word ending with its hyphen.
+pub(crate) fn lines(words: Vec<Word>, width_bound: f32, text_len: usize) -> Vec<Line> {
+    let mut lines: Vec<Line> = Vec::new();
+    let mut line = Line::default();
+    let mut caret = 0.0;
+    let mut hyphen = None;
+
+    for word in words {
+        // Drop trailing spaces when bounds-wrapping.
+        // However, if the word ends in a hard-break "Foo  \n" keep the trailing space width.
+        let word_wrap_width = match word.hard_break {
+            false => word.layout_width_no_trail,
+            true => word.layout_width,
+        };
+        let hyphen_width = word.hyphen.as_ref().map_or(0.0, |h| h.advance);
+
+        let word_right = caret + word_wrap_width + hyphen_width;
+        // Reduce float errors by using relative "<= width bound" check
+        let word_in_bounds = word_right <= width_bound * (1.0 + f32::EPSILON);
+
+        // the first word is allowed to overlap the bounds
+        if !word_in_bounds && !line.glyphs.is_empty() {
+            if let Some(hyphen) = hyphen.take() {
+                line.push_hyphen(hyphen);
+            }
+            lines.push(std::mem::take(&mut line));
+            caret = 0.0;
+        }
+
+        line.rightmost = caret + word_wrap_width;
+        line.max_v_metrics = line.max_v_metrics.max(word.max_v_metrics);
+        line.glyphs.extend(word.glyphs);
+        caret += word.layout_width;
+        line.width = caret;
+        hyphen = word.hyphen;
+
+        if word.hard_break {
+            line.paragraph_end = true;
+            lines.push(std::mem::take(&mut line));
+            caret = 0.0;
+        }
+    }
+
+    if !line.glyphs.is_empty() {
+        line.paragraph_end = true;
+        lines.push(line);
+    }
+
+    // lines cover the text up to where the next one starts
+    let mut end = text_len;
+    for line in lines.iter_mut().rev() {
+        let start = line.glyphs.first().map_or(end, |g| g.offset);
+        line.range = start..end;
+        end = start;
+    }
+    if let Some(first) = lines.first_mut() {
+        first.range.start = 0;
+    }
+
+    lines
+}
+
+/// Returns glyph indices in visual order, reversing each run of glyphs at or
+/// above every odd level, from the highest level down.
+pub(crate) fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+
+    let max_level = levels.iter().copied().max().unwrap_or(0);
+    let min_odd_level = levels.iter().copied().min().unwrap_or(0) | 1;
+
+    for level in (min_odd_level..=max_level).rev() {
+        let mut index = 0;
+        while index < order.len() {
+            if levels[order[index]] < level {
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+            while index < order.len() && levels[order[index]] >= level {
+                index += 1;
+            }
+            order[start..index].reverse();
+        }
+    }
+
+    order
+}
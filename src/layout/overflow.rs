@@ -0,0 +1,217 @@
+use super::{lines::Line, shaper::ShapedGlyph};
+use crate::ab_glyph::Font;
+use glyph_brush::SectionGeometry;
+use std::hash::{Hash, Hasher};
+
+/// How a [`ShapedLayout`](struct.ShapedLayout.html) handles text
+/// overflowing its bounds.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Overflow {
+    /// Glyphs are clipped at the bounds when drawn.
+    #[default]
+    Clip,
+    /// Lines overflowing the bounds width are truncated to end with an
+    /// ellipsis, as is the last line fitting the bounds height of wrapped
+    /// text when more lines follow.
+    Ellipsis,
+    /// Lines overflowing the bounds width keep their start & end, with an
+    /// ellipsis in between.
+    MiddleEllipsis,
+    /// Glyphs fade out over this width in pixels before the right bound.
+    ///
+    /// Fading is applied when drawing sections laid out by the default
+    /// layout of a `GlyphBrush`, see
+    /// [`GlyphBrushBuilder::overflow`](struct.GlyphBrushBuilder.html#method.overflow),
+    /// or queued with a layout by
+    /// [`GlyphBrush::queue_shaped`](struct.GlyphBrush.html#method.queue_shaped).
+    Fade(f32),
+}
+
+impl Overflow {
+    /// Returns the fade out width, `0` if not fading.
+    #[inline]
+    pub(crate) fn fade(&self) -> f32 {
+        match *self {
+            Overflow::Fade(width) => width,
+            _ => 0.0,
+        }
+    }
+
+    /// Truncates lines overflowing the bounds, inserting ellipses.
+    pub(crate) fn apply<F: Font>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        wrap: bool,
+        lines: &mut Vec<Line>,
+    ) {
+        let (bound_w, bound_h) = geometry.bounds;
+        let overflows = |line: &Line| line.rightmost > bound_w * (1.0 + f32::EPSILON);
+
+        match self {
+            Overflow::Ellipsis => {
+                let mut ellipsized = None;
+
+                if wrap && bound_h.is_finite() {
+                    let mut height = 0.0;
+                    let fitting = lines
+                        .iter()
+                        .take_while(|line| {
+                            height += line.line_height();
+                            height <= bound_h * (1.0 + f32::EPSILON)
+                        })
+                        .count()
+                        .max(1);
+
+                    if fitting < lines.len() {
+                        lines.truncate(fitting);
+                        ellipsized = Some(fitting - 1);
+                    }
+                }
+
+                for (index, line) in lines.iter_mut().enumerate() {
+                    if overflows(line) || ellipsized == Some(index) {
+                        let ellipsis = ellipsis(fonts, line, line.glyphs.len());
+                        line.truncate_end(bound_w, ellipsis);
+                    }
+                }
+            }
+            Overflow::MiddleEllipsis => {
+                for line in lines.iter_mut().filter(|line| overflows(line)) {
+                    let ellipsis = ellipsis(fonts, line, line.glyphs.len() / 2);
+                    line.truncate_middle(bound_w, ellipsis);
+                }
+            }
+            Overflow::Clip | Overflow::Fade(_) => {}
+        }
+    }
+}
+
+impl PartialEq for Overflow {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Overflow::Fade(a), Overflow::Fade(b)) => a.to_bits() == b.to_bits(),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Overflow {}
+
+impl Hash for Overflow {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let Overflow::Fade(width) = self {
+            width.to_bits().hash(state);
+        }
+    }
+}
+
+/// Returns an ellipsis in the font & scale of the line's glyph near `index`,
+/// a single "…" or three full stops if the font lacks it.
+fn ellipsis<F: Font>(fonts: &[F], line: &Line, index: usize) -> Vec<ShapedGlyph> {
+    let template = match line.glyphs.get(index.saturating_sub(1)) {
+        Some(glyph) => glyph,
+        None => return Vec::new(),
+    };
+
+    let (glyph, count) = match template.with_char(fonts, '…') {
+        Some(glyph) => (glyph, 1),
+        None => match template.with_char(fonts, '.') {
+            Some(glyph) => (glyph, 3),
+            None => return Vec::new(),
+        },
+    };
+
+    vec![
+        ShapedGlyph {
+            level: line.level,
+            ..glyph
+        };
+        count
+    ]
+}
+
+impl Line {
+    /// Keeps the glyphs fitting `width` along with the ellipsis at the end.
+    pub(crate) fn truncate_end(&mut self, width: f32, ellipsis: Vec<ShapedGlyph>) {
+        let available = width - advance(&ellipsis);
+
+        let mut keep = fitting(self.glyphs.iter(), available);
+        keep = cluster_start(&self.glyphs, keep);
+        keep = trim_whitespace(&self.glyphs, keep);
+
+        self.glyphs.truncate(keep);
+        self.glyphs.extend(ellipsis);
+        self.measure();
+    }
+
+    /// Keeps the glyphs at either end fitting `width` along with the ellipsis
+    /// in between.
+    pub(crate) fn truncate_middle(&mut self, width: f32, ellipsis: Vec<ShapedGlyph>) {
+        let available = width - advance(&ellipsis);
+        let content_end = trim_whitespace(&self.glyphs, self.glyphs.len());
+
+        let mut prefix = fitting(self.glyphs.iter(), available / 2.0);
+        prefix = cluster_start(&self.glyphs, prefix);
+        let prefix_width = advance(&self.glyphs[..prefix]);
+
+        let suffix_len = fitting(
+            self.glyphs[prefix..content_end].iter().rev(),
+            available - prefix_width,
+        );
+        let mut suffix = content_end - suffix_len;
+        while suffix > 0
+            && suffix < content_end
+            && self.glyphs[suffix].offset == self.glyphs[suffix - 1].offset
+        {
+            suffix += 1;
+        }
+
+        let prefix = trim_whitespace(&self.glyphs, prefix);
+        let tail: Vec<_> = self.glyphs.drain(suffix..content_end).collect();
+        self.glyphs.truncate(prefix);
+        self.glyphs.extend(ellipsis);
+        self.glyphs.extend(tail);
+        self.measure();
+    }
+
+    fn measure(&mut self) {
+        let content_end = trim_whitespace(&self.glyphs, self.glyphs.len());
+        self.rightmost = advance(&self.glyphs[..content_end]);
+        self.width = advance(&self.glyphs);
+    }
+}
+
+fn advance(glyphs: &[ShapedGlyph]) -> f32 {
+    glyphs.iter().map(|g| g.advance).sum()
+}
+
+/// Returns how many of the glyphs fit `width`.
+fn fitting<'a>(glyphs: impl Iterator<Item = &'a ShapedGlyph>, width: f32) -> usize {
+    let mut caret = 0.0;
+    glyphs
+        .take_while(|g| {
+            caret += g.advance;
+            caret <= width
+        })
+        .count()
+}
+
+/// Moves a glyph count back to the start of the cluster it splits.
+fn cluster_start(glyphs: &[ShapedGlyph], mut count: usize) -> usize {
+    while count > 0 && count < glyphs.len() && glyphs[count].offset == glyphs[count - 1].offset {
+        count -= 1;
+    }
+    count
+}
+
+/// Moves a glyph count back before trailing whitespace.
+fn trim_whitespace(glyphs: &[ShapedGlyph], mut count: usize) -> usize {
+    while count > 0 && glyphs[count - 1].whitespace {
+        count -= 1;
+    }
+    count
+}
@@ -0,0 +1,33 @@
+use crate::ab_glyph::{point, Font, Glyph, Point};
+
+/// Positions the glyph so that its origin lands on `origin` once its quad is
+/// rotated clockwise by `angle` radians about its center, as the vertex
+/// shader does for `Instance::rotation`.
+pub(crate) fn rotated_about_center<F: Font>(
+    font: &F,
+    mut glyph: Glyph,
+    origin: Point,
+    angle: f32,
+) -> Glyph {
+    let (sin, cos) = angle.sin_cos();
+    let rotate = |p: Point| point(cos * p.x - sin * p.y, sin * p.x + cos * p.y);
+
+    // the quad center depends on pixel rounding at the subpixel position, so
+    // place the glyph once more at its first estimate
+    glyph.position = origin;
+    for _ in 0..2 {
+        let center = match font.outline_glyph(glyph.clone()) {
+            Some(outlined) => {
+                let bounds = outlined.px_bounds();
+                point(
+                    (bounds.min.x + bounds.max.x) / 2.0 - glyph.position.x,
+                    (bounds.min.y + bounds.max.y) / 2.0 - glyph.position.y,
+                )
+            }
+            None => return glyph,
+        };
+        glyph.position = origin - center + rotate(center);
+    }
+
+    glyph
+}
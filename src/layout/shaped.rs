@@ -0,0 +1,754 @@
+use super::{
+    bidi::{reorder_line, BidiLine, BidiMap, TextDirection},
+    faces::Faces,
+    fallback::Fallback,
+    lines::{lines, Line},
+    overflow::Overflow,
+    shaper::{concat, shape},
+    spacing::{Spacing, Spacings},
+    words::words,
+};
+use crate::{
+    ab_glyph::{point, Font, Rect, ScaleFont},
+    FontFace, Variation,
+};
+use glyph_brush::{
+    BuiltInLineBreaker, FontId, GlyphChange, GlyphPositioner, HorizontalAlign, Layout, LineBreaker,
+    SectionGeometry, SectionGlyph, ToSectionText, VerticalAlign,
+};
+use unicode_bidi::BidiInfo;
+
+/// A [`GlyphPositioner`](trait.GlyphPositioner.html) shaping text with
+/// OpenType `GSUB`/`GPOS` features, so that ligatures, contextual forms &
+/// mark positioning of scripts such as Arabic or Devanagari render correctly.
+///
+/// Shaping is done by [rustybuzz](https://docs.rs/rustybuzz), text is wrapped,
+/// aligned & bounded like the wrapped [`Layout`](enum.Layout.html).
+///
+/// Use with [`queue_custom_layout`](struct.GlyphBrush.html#method.queue_custom_layout),
+/// or build with
+/// [`GlyphBrushBuilder::shaping`](struct.GlyphBrushBuilder.html#method.shaping)
+/// to shape every queued section.
+///
+/// Bidirectional text is reordered visually per line following the Unicode
+/// Bidirectional Algorithm, see [`direction`](#method.direction). Alignment
+/// stays physical for right-to-left paragraphs, e.g. `HorizontalAlign::Right`
+/// aligns them to their start edge, matching
+/// [`bounds_rect`](trait.GlyphPositioner.html#tymethod.bounds_rect).
+///
+/// Characters missing from a section's font are rendered with the first
+/// font of its [fallback chain](#method.fallback_fonts) that has them.
+///
+/// Fonts are shaped from their [registered faces](#method.font_face), fonts
+/// without one fall back to a one to one character to glyph mapping, without
+/// ligatures, kerning or mark positioning. Fonts added with
+/// [`GlyphBrush::add_font_variation`](struct.GlyphBrush.html#method.add_font_variation)
+/// are shaped at their [variation coordinates](#method.font_variations).
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ShapedLayout<L: LineBreaker = BuiltInLineBreaker> {
+    layout: Layout<L>,
+    direction: TextDirection,
+    fallback: Fallback,
+    faces: Faces,
+    spacing: Spacings,
+    justify: bool,
+    hanging_punctuation: bool,
+    overflow: Overflow,
+}
+
+impl Default for ShapedLayout {
+    #[inline]
+    fn default() -> Self {
+        ShapedLayout::default_wrap()
+    }
+}
+
+impl ShapedLayout {
+    #[inline]
+    pub fn default_single_line() -> Self {
+        Layout::default_single_line().into()
+    }
+
+    #[inline]
+    pub fn default_wrap() -> Self {
+        Layout::default_wrap().into()
+    }
+}
+
+impl<L: LineBreaker> From<Layout<L>> for ShapedLayout<L> {
+    #[inline]
+    fn from(layout: Layout<L>) -> Self {
+        ShapedLayout {
+            layout,
+            direction: TextDirection::default(),
+            fallback: Fallback::default(),
+            faces: Faces::default(),
+            spacing: Spacings::default(),
+            justify: false,
+            hanging_punctuation: false,
+            overflow: Overflow::default(),
+        }
+    }
+}
+
+impl<L: LineBreaker> ShapedLayout<L> {
+    /// Returns an identical `ShapedLayout` with a new horizontal alignment.
+    pub fn h_align(self, h_align: HorizontalAlign) -> Self {
+        ShapedLayout {
+            layout: self.layout.h_align(h_align),
+            ..self
+        }
+    }
+
+    /// Returns an identical `ShapedLayout` with a new vertical alignment.
+    pub fn v_align(self, v_align: VerticalAlign) -> Self {
+        ShapedLayout {
+            layout: self.layout.v_align(v_align),
+            ..self
+        }
+    }
+
+    /// Returns an identical `ShapedLayout` with a new line breaker.
+    pub fn line_breaker<L2: LineBreaker>(self, line_breaker: L2) -> ShapedLayout<L2> {
+        self.with_layout(self.layout.line_breaker(line_breaker))
+    }
+
+    /// Returns an identical `ShapedLayout` with a new base paragraph direction.
+    ///
+    /// Defaults to [`TextDirection::Auto`](enum.TextDirection.html#variant.Auto).
+    pub fn direction(self, direction: TextDirection) -> Self {
+        ShapedLayout { direction, ..self }
+    }
+
+    /// Returns an identical `ShapedLayout` with a new font fallback chain.
+    ///
+    /// Characters missing from a section's font are rendered with the first
+    /// of these fonts that has them, splitting runs as needed. `FontId`s index
+    /// [`GlyphBrush::fonts`](struct.GlyphBrush.html#method.fonts).
+    pub fn fallback_fonts(mut self, fonts: Vec<FontId>) -> Self {
+        self.fallback.fonts = fonts;
+        self
+    }
+
+    /// Returns an identical `ShapedLayout` with a font fallback chain used
+    /// for the section `Text` at `section_index` instead of the
+    /// [default one](#method.fallback_fonts).
+    pub fn section_fallback_fonts(mut self, section_index: usize, fonts: Vec<FontId>) -> Self {
+        self.fallback
+            .sections
+            .retain(|(index, _)| *index != section_index);
+        self.fallback.sections.push((section_index, fonts));
+        self
+    }
+
+    /// Returns an identical `ShapedLayout` justifying wrapped lines, word gaps
+    /// stretching so that lines fill the bounds width.
+    ///
+    /// The last line of each paragraph is not stretched, staying aligned to
+    /// the start edge of the justified lines, i.e. to the left of left-to-right
+    /// paragraphs. The horizontal alignment positions the block of lines.
+    ///
+    /// Has no effect on single line layouts & unbounded widths.
+    pub fn justify(self, justify: bool) -> Self {
+        ShapedLayout { justify, ..self }
+    }
+
+    /// Returns an identical `ShapedLayout` letting full stops & commas, such
+    /// as `。` & `、`, hang past the end of wrapped lines instead of being
+    /// carried to the next line along with the character before them.
+    ///
+    /// Hanging punctuation is left out of alignment & justification, see
+    /// [`KinsokuLineBreaker`](struct.KinsokuLineBreaker.html) for the line
+    /// breaking rules of Japanese text.
+    ///
+    /// Defaults to `false`.
+    pub fn hanging_punctuation(self, hanging_punctuation: bool) -> Self {
+        ShapedLayout {
+            hanging_punctuation,
+            ..self
+        }
+    }
+
+    /// Returns an identical `ShapedLayout` with a new way of handling text
+    /// overflowing the bounds.
+    ///
+    /// Defaults to [`Overflow::Clip`](enum.Overflow.html#variant.Clip).
+    pub fn overflow(self, overflow: Overflow) -> Self {
+        ShapedLayout { overflow, ..self }
+    }
+
+    /// Returns the way of handling text overflowing the bounds.
+    #[inline]
+    pub(crate) fn overflow_mode(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// Returns an identical `ShapedLayout` with new letter spacing, word
+    /// spacing & line height.
+    ///
+    /// Defaults to the font's own metrics, see
+    /// [`Spacing::default`](struct.Spacing.html).
+    pub fn spacing(mut self, spacing: Spacing) -> Self {
+        self.spacing.spacing = spacing;
+        self
+    }
+
+    /// Returns an identical `ShapedLayout` with spacing used for the section
+    /// `Text` at `section_index` instead of the [default one](#method.spacing).
+    pub fn section_spacing(mut self, section_index: usize, spacing: Spacing) -> Self {
+        self.spacing
+            .sections
+            .retain(|(index, _)| *index != section_index);
+        self.spacing.sections.push((section_index, spacing));
+        self
+    }
+
+    /// Returns an identical `ShapedLayout` shaping the font `font_id` with
+    /// the face, which must be the face of that font.
+    ///
+    /// Set by
+    /// [`GlyphBrush::add_font_face`](struct.GlyphBrush.html#method.add_font_face)
+    /// for the default layout & the layouts queued through the brush.
+    pub fn font_face(mut self, font_id: FontId, face: FontFace) -> Self {
+        self.faces.insert(font_id, face);
+        self
+    }
+
+    /// Returns an identical `ShapedLayout` shaping the variable font
+    /// `font_id` at the axis coordinates, matching the instance it was
+    /// created with.
+    ///
+    /// Set by
+    /// [`GlyphBrush::add_font_variation`](struct.GlyphBrush.html#method.add_font_variation)
+    /// for the default layout & the layouts queued through the brush.
+    pub fn font_variations(mut self, font_id: FontId, variations: Vec<Variation>) -> Self {
+        self.faces.insert_variations(font_id, variations);
+        self
+    }
+
+    /// Returns an identical `ShapedLayout` with the faces & variation
+    /// coordinates of fonts it has none set for.
+    pub(crate) fn with_faces(mut self, faces: &Faces) -> Self {
+        self.faces.extend(faces);
+        self
+    }
+
+    /// Returns a `ShapedLayout` with the options of this one, wrapping
+    /// another layout.
+    pub(crate) fn with_layout<L2: LineBreaker>(&self, layout: Layout<L2>) -> ShapedLayout<L2> {
+        ShapedLayout {
+            layout,
+            direction: self.direction,
+            fallback: self.fallback.clone(),
+            faces: self.faces.clone(),
+            spacing: self.spacing.clone(),
+            justify: self.justify,
+            hanging_punctuation: self.hanging_punctuation,
+            overflow: self.overflow,
+        }
+    }
+
+    /// Returns the logical to visual character order of each line the
+    /// sections are laid out into, for cursor placement & hit-testing.
+    pub fn bidi_map<F, S>(&self, fonts: &[F], geometry: &SectionGeometry, sections: &[S]) -> BidiMap
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let text = concat(sections);
+        let bidi = BidiInfo::new(&text, self.direction.level());
+
+        let section_ends: Vec<_> = sections
+            .iter()
+            .scan(0, |end, s| {
+                *end += s.to_section_text().text.len();
+                Some(*end)
+            })
+            .collect();
+
+        BidiMap {
+            lines: self
+                .lines(fonts, geometry, sections, &bidi)
+                .into_iter()
+                .map(|line| BidiLine::new(&bidi, line.range, &section_ends))
+                .collect(),
+        }
+    }
+
+    fn justifies(&self, geometry: &SectionGeometry) -> bool {
+        self.justify && matches!(self.layout, Layout::Wrap { .. }) && geometry.bounds.0.is_finite()
+    }
+
+    fn lines<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+        bidi: &BidiInfo<'_>,
+    ) -> Vec<Line>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let (line_breaker, single_line) = match self.layout {
+            Layout::SingleLine { line_breaker, .. } => (line_breaker, true),
+            Layout::Wrap { line_breaker, .. } => (line_breaker, false),
+        };
+
+        let mut glyphs = shape(fonts, sections, bidi, &self.fallback, &self.faces);
+        self.spacing.apply(&mut glyphs);
+        // single lines are ellipsized rather than cut at a word boundary
+        let width_bound = match self.overflow {
+            Overflow::Ellipsis | Overflow::MiddleEllipsis if single_line => f32::INFINITY,
+            _ => geometry.bounds.0,
+        };
+        let mut lines = lines(
+            words(
+                fonts,
+                glyphs,
+                bidi.text,
+                &line_breaker,
+                self.hanging_punctuation,
+            ),
+            width_bound,
+            bidi.text.len(),
+        );
+        if single_line {
+            lines.truncate(1);
+        }
+
+        for line in &mut lines {
+            reorder_line(bidi, line);
+        }
+        self.overflow
+            .apply(fonts, geometry, !single_line, &mut lines);
+
+        if self.justifies(geometry) {
+            for line in lines.iter_mut().filter(|line| !line.paragraph_end) {
+                line.justify(geometry.bounds.0);
+            }
+        }
+
+        lines
+    }
+}
+
+impl<L: LineBreaker> GlyphPositioner for ShapedLayout<L> {
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let SectionGeometry {
+            screen_position,
+            bounds: (bound_w, bound_h),
+        } = *geometry;
+
+        let (h_align, v_align) = match self.layout {
+            Layout::SingleLine {
+                h_align, v_align, ..
+            }
+            | Layout::Wrap {
+                h_align, v_align, ..
+            } => (h_align, v_align),
+        };
+
+        let text = concat(sections);
+        let bidi = BidiInfo::new(&text, self.direction.level());
+        let lines = self.lines(fonts, geometry, sections, &bidi);
+
+        let mut out = vec![];
+        let mut caret = screen_position;
+        let v_align_top = v_align == VerticalAlign::Top;
+        let justify = self.justifies(geometry);
+
+        for line in lines {
+            // top align can bound check & exit early
+            if v_align_top && caret.1 >= screen_position.1 + bound_h {
+                break;
+            }
+
+            let line_height = line.line_height();
+            if justify && line.paragraph_end {
+                // align to the start edge of the justified lines
+                let left = match h_align {
+                    HorizontalAlign::Left => caret.0,
+                    HorizontalAlign::Center => caret.0 - bound_w / 2.0,
+                    HorizontalAlign::Right => caret.0 - bound_w,
+                };
+                out.extend(match line.level % 2 {
+                    1 => line.aligned_on_screen((left + bound_w, caret.1), HorizontalAlign::Right),
+                    _ => line.aligned_on_screen((left, caret.1), HorizontalAlign::Left),
+                });
+            } else {
+                out.extend(line.aligned_on_screen(caret, h_align));
+            }
+            caret.1 += line_height;
+        }
+
+        if !out.is_empty() && !v_align_top {
+            let shift_up = if v_align == VerticalAlign::Center {
+                (caret.1 - screen_position.1) / 2.0
+            } else {
+                caret.1 - screen_position.1
+            };
+
+            let Rect { min, max } = self.bounds_rect(geometry);
+
+            out.retain_mut(|sg| {
+                // shift into position
+                sg.glyph.position.y -= shift_up;
+
+                // filter away out-of-bounds glyphs
+                let sfont = fonts[sg.font_id].as_scaled(sg.glyph.scale);
+                let h_advance = sfont.h_advance(sg.glyph.id);
+                let h_side_bearing = sfont.h_side_bearing(sg.glyph.id);
+                let height = sfont.height();
+
+                sg.glyph.position.x - h_side_bearing <= max.x
+                    && sg.glyph.position.x + h_advance >= min.x
+                    && sg.glyph.position.y - height <= max.y
+                    && sg.glyph.position.y + height >= min.y
+            });
+        }
+
+        out
+    }
+
+    #[inline]
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        self.layout.bounds_rect(geometry)
+    }
+
+    fn recalculate_glyphs<F, S, P>(
+        &self,
+        previous: P,
+        change: GlyphChange,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+        P: IntoIterator<Item = SectionGlyph>,
+    {
+        match change {
+            GlyphChange::Geometry(old) if old.bounds == geometry.bounds => {
+                // position change
+                let adjustment = point(
+                    geometry.screen_position.0 - old.screen_position.0,
+                    geometry.screen_position.1 - old.screen_position.1,
+                );
+
+                previous
+                    .into_iter()
+                    .map(|mut sg| {
+                        sg.glyph.position += adjustment;
+                        sg
+                    })
+                    .collect()
+            }
+            _ => self.calculate_glyphs(fonts, geometry, sections),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ab_glyph::FontRef, HyphenationLineBreaker, Language};
+    use glyph_brush::{ab_glyph::PxScale, SectionText};
+
+    const FONT: &[u8] = include_bytes!("../../examples/Inconsolata-Regular.ttf");
+    const SCALE: PxScale = PxScale { x: 20.0, y: 20.0 };
+
+    fn font() -> FontRef<'static> {
+        FontRef::try_from_slice(FONT).unwrap()
+    }
+
+    /// Returns the advance of every glyph of the monospaced font.
+    fn advance() -> f32 {
+        let font = font();
+        font.as_scaled(SCALE).h_advance(font.glyph_id('a'))
+    }
+
+    /// Lays the text out within `columns` glyph advances & `rows` lines.
+    fn glyphs<L: LineBreaker>(
+        layout: &ShapedLayout<L>,
+        text: &str,
+        columns: f32,
+        rows: f32,
+    ) -> Vec<SectionGlyph> {
+        let font = font();
+        let height = font.as_scaled(SCALE).height();
+        layout.calculate_glyphs(
+            &[font],
+            &SectionGeometry {
+                screen_position: (0.0, 0.0),
+                bounds: (columns * advance(), rows * height),
+            },
+            &[SectionText {
+                text,
+                scale: SCALE,
+                font_id: FontId(0),
+            }],
+        )
+    }
+
+    /// Returns each line's characters laid out by `glyphs`, from left to
+    /// right, with the x of the first in advances.
+    fn laid_out<L: LineBreaker>(
+        layout: &ShapedLayout<L>,
+        text: &str,
+        columns: f32,
+        rows: f32,
+    ) -> Vec<(f32, String)> {
+        let font = font();
+        let glyphs = glyphs(layout, text, columns, rows);
+
+        let mut lines: Vec<(f32, Vec<(f32, char)>)> = Vec::new();
+        for sg in glyphs {
+            let c = text
+                .chars()
+                .chain("…‐".chars())
+                .find(|c| font.glyph_id(*c) == sg.glyph.id)
+                .unwrap_or('?');
+            let x = sg.glyph.position.x / advance();
+            let y = sg.glyph.position.y;
+            match lines.iter_mut().find(|(line_y, _)| *line_y == y) {
+                Some((_, line)) => line.push((x, c)),
+                None => lines.push((y, vec![(x, c)])),
+            }
+        }
+
+        lines
+            .into_iter()
+            .map(|(_, mut line)| {
+                line.sort_by(|a, b| a.0.total_cmp(&b.0));
+                (line[0].0, line.into_iter().map(|(_, c)| c).collect())
+            })
+            .collect()
+    }
+
+    fn text_of(lines: Vec<(f32, String)>) -> Vec<String> {
+        lines.into_iter().map(|(_, line)| line).collect()
+    }
+
+    #[test]
+    fn wraps_at_words() {
+        let layout = ShapedLayout::default_wrap();
+        assert_eq!(
+            text_of(laid_out(&layout, "lorem ipsum dolor", 12.0, 10.0)),
+            ["lorem ipsum ", "dolor"]
+        );
+    }
+
+    #[test]
+    fn ellipsizes_single_lines() {
+        let layout = ShapedLayout::default_single_line().overflow(Overflow::Ellipsis);
+        assert_eq!(
+            text_of(laid_out(&layout, "lorem ipsum dolor", 9.5, 1.0)),
+            ["lorem ip…"]
+        );
+        // trailing whitespace is trimmed before the ellipsis
+        assert_eq!(
+            text_of(laid_out(&layout, "lorem ipsum dolor", 7.5, 1.0)),
+            ["lorem…"]
+        );
+        // fitting lines are left alone
+        assert_eq!(text_of(laid_out(&layout, "lorem", 9.0, 1.0)), ["lorem"]);
+    }
+
+    #[test]
+    fn ellipsizes_the_middle() {
+        let layout = ShapedLayout::default_single_line().overflow(Overflow::MiddleEllipsis);
+        assert_eq!(
+            text_of(laid_out(&layout, "abcdefghijkl", 7.5, 1.0)),
+            ["abc…jkl"]
+        );
+        // no glyph fits half the room left, the whole content fits all of it
+        assert_eq!(text_of(laid_out(&layout, "a   ", 2.5, 1.0)), ["…a"]);
+    }
+
+    #[test]
+    fn ellipsizes_the_last_fitting_line() {
+        let layout = ShapedLayout::default_wrap().overflow(Overflow::Ellipsis);
+        assert_eq!(
+            text_of(laid_out(&layout, "lorem ipsum dolor sit", 12.5, 1.5)),
+            ["lorem ipsum…"]
+        );
+    }
+
+    #[test]
+    fn justifies_all_but_paragraph_ends() {
+        let layout = ShapedLayout::default_wrap().justify(true);
+        let lines = laid_out(&layout, "aa bb cc dd\nee ff", 10.0, 10.0);
+        assert_eq!(text_of(lines), ["aa bb cc ", "dd", "ee ff"]);
+
+        let glyphs = glyphs(&layout, "aa bb cc dd\nee ff", 10.0, 10.0);
+        let x_of = |byte_index: usize| {
+            glyphs
+                .iter()
+                .find(|sg| sg.byte_index == byte_index)
+                .map(|sg| sg.glyph.position.x / advance())
+                .unwrap()
+        };
+        // 2 gaps stretched from 1 to 2 advances, filling 10 from 8
+        assert!((x_of(3) - 4.0).abs() < 1e-3);
+        assert!((x_of(7) - 9.0).abs() < 1e-3);
+        // the last lines of paragraphs keep their spacing
+        assert!((x_of(12) - 0.0).abs() < 1e-3);
+        assert!((x_of(15) - 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ends_hyphenated_lines_with_a_hyphen() {
+        let layout = ShapedLayout::default_wrap()
+            .line_breaker(HyphenationLineBreaker::new(Language::EnglishUS));
+        assert_eq!(
+            text_of(laid_out(&layout, "hyphenation", 8.0, 10.0)),
+            ["hyphena‐", "tion"]
+        );
+    }
+
+    /// Returns the section, byte index & position of each glyph.
+    fn placed(layout: &ShapedLayout, texts: &[&str]) -> Vec<(usize, usize, f32, f32)> {
+        let sections: Vec<_> = texts
+            .iter()
+            .map(|text| SectionText {
+                text,
+                scale: SCALE,
+                font_id: FontId(0),
+            })
+            .collect();
+        layout
+            .calculate_glyphs(&[font()], &SectionGeometry::default(), &sections)
+            .into_iter()
+            .map(|sg| {
+                let position = sg.glyph.position;
+                (sg.section_index, sg.byte_index, position.x, position.y)
+            })
+            .collect()
+    }
+
+    /// Asserts the glyph is placed after `advances` advances & `ems` ems of
+    /// spacing.
+    fn assert_x(
+        placed: &[(usize, usize, f32, f32)],
+        (section, byte_index): (usize, usize),
+        advances: f32,
+        ems: f32,
+    ) {
+        let x = placed
+            .iter()
+            .find(|(s, i, _, _)| (*s, *i) == (section, byte_index))
+            .map(|(_, _, x, _)| *x)
+            .unwrap();
+        let expected = advances * advance() + ems * SCALE.x;
+        assert!((x - expected).abs() < 1e-3, "{} != {}", x, expected);
+    }
+
+    #[test]
+    fn spaces_letters_and_words() {
+        let layout = ShapedLayout::default_wrap().spacing(Spacing::default().letter(0.5));
+        let letters = placed(&layout, &["abc"]);
+        assert_x(&letters, (0, 1), 1.0, 0.5);
+        assert_x(&letters, (0, 2), 2.0, 1.0);
+
+        let layout = ShapedLayout::default_wrap().spacing(Spacing::default().word(1.0));
+        let words = placed(&layout, &["a b c"]);
+        assert_x(&words, (0, 1), 1.0, 0.0);
+        assert_x(&words, (0, 2), 2.0, 1.0);
+        assert_x(&words, (0, 4), 4.0, 2.0);
+    }
+
+    #[test]
+    fn spaces_sections_of_their_own() {
+        let layout = ShapedLayout::default_wrap()
+            .spacing(Spacing::default().letter(0.5))
+            .section_spacing(1, Spacing::default());
+        let placed = placed(&layout, &["ab", "cd", "ef"]);
+        assert_x(&placed, (1, 0), 2.0, 1.0);
+        assert_x(&placed, (1, 1), 3.0, 1.0);
+        assert_x(&placed, (2, 0), 4.0, 1.0);
+        assert_x(&placed, (2, 1), 5.0, 1.5);
+    }
+
+    #[test]
+    fn splits_leading_around_lines() {
+        let scaled = font().as_scaled(SCALE).height();
+        let y_of = |layout: &ShapedLayout| {
+            let positions = placed(layout, &["a\nb"]);
+            (positions[0].3, positions[positions.len() - 1].3)
+        };
+
+        let (first, second) = y_of(&ShapedLayout::default_wrap());
+        assert!((second - first - scaled).abs() < 1e-3);
+
+        let layout = ShapedLayout::default_wrap().spacing(Spacing::default().line_height(2.0));
+        let (spaced_first, spaced_second) = y_of(&layout);
+        assert!((spaced_second - spaced_first - 2.0 * scaled).abs() < 1e-3);
+        assert!((spaced_first - first - scaled / 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn reorders_bidi_lines() {
+        let font = font();
+        let layout = ShapedLayout::default_wrap();
+        let map = layout.bidi_map(
+            &[font],
+            &SectionGeometry::default(),
+            &[SectionText {
+                text: "ab \u{5d0}\u{5d1} c",
+                scale: SCALE,
+                font_id: FontId(0),
+            }],
+        );
+
+        assert_eq!(map.lines.len(), 1);
+        let line = &map.lines[0];
+        assert_eq!(line.paragraph_level, 0);
+        assert_eq!(line.levels, [0, 0, 0, 1, 1, 0, 0]);
+        assert_eq!(line.visual_to_logical, [0, 1, 2, 4, 3, 5, 6]);
+        assert_eq!(line.logical_to_visual, [0, 1, 2, 4, 3, 5, 6]);
+        assert_eq!(line.chars[3], (0, 3));
+        assert_eq!(line.chars[4], (0, 5));
+        assert!(line.is_rtl(3) && !line.is_rtl(2));
+    }
+
+    #[test]
+    fn reorders_rtl_paragraphs() {
+        let font = font();
+        let layout = ShapedLayout::default_wrap().direction(TextDirection::RightToLeft);
+        let map = layout.bidi_map(
+            &[font],
+            &SectionGeometry::default(),
+            &[
+                SectionText {
+                    text: "ab ",
+                    scale: SCALE,
+                    font_id: FontId(0),
+                },
+                SectionText {
+                    text: "\u{5d0}",
+                    scale: SCALE,
+                    font_id: FontId(0),
+                },
+            ],
+        );
+
+        let line = &map.lines[0];
+        assert_eq!(line.paragraph_level, 1);
+        assert_eq!(line.levels, [2, 2, 1, 1]);
+        // runs are placed right to left, left-to-right ones kept in order
+        assert_eq!(line.visual_to_logical, [3, 2, 0, 1]);
+        assert_eq!(line.logical_to_visual, [2, 3, 1, 0]);
+        assert_eq!(line.chars, [(0, 0), (0, 1), (0, 2), (1, 0)]);
+    }
+}
@@ -0,0 +1,509 @@
+use super::{
+    faces::Faces,
+    fallback::{select_font, Fallback},
+    words::VMetrics,
+};
+use crate::ab_glyph::{point, Font, Glyph, GlyphId, PxScale, ScaleFont};
+use glyph_brush::{FontId, SectionGlyph, SectionText, ToSectionText};
+use rustybuzz::{ttf_parser::Tag, Direction, Face, UnicodeBuffer};
+use std::{collections::HashMap, ops::Range};
+use unicode_bidi::{BidiInfo, Level};
+use unicode_script::{Script, UnicodeScript};
+
+pub(crate) const SOFT_HYPHEN: char = '\u{AD}';
+
+/// A glyph output by the shaper, positioned relative to its pen position.
+#[derive(Debug, Clone)]
+pub(crate) struct ShapedGlyph {
+    /// The glyph, with a position holding its offset from the pen.
+    pub glyph: SectionGlyph,
+    /// Horizontal pen advance after drawing this glyph.
+    pub advance: f32,
+    /// Byte index of the glyph's cluster in the concatenated text.
+    pub offset: usize,
+    /// Bidi embedding level, odd levels are right-to-left.
+    pub level: u8,
+    pub v_metrics: VMetrics,
+    pub control: bool,
+    pub whitespace: bool,
+    /// Not shaped from the text, e.g. an ellipsis or a hyphen, with the
+    /// byte index of the glyph it was made from.
+    pub inserted: bool,
+}
+
+impl ShapedGlyph {
+    /// Returns a glyph of the character in the font & scale of this one, if
+    /// the font has it.
+    pub fn with_char<F: Font>(&self, fonts: &[F], c: char) -> Option<ShapedGlyph> {
+        let font = &fonts[self.glyph.font_id];
+        let id = font.glyph_id(c);
+        if id.0 == 0 {
+            return None;
+        }
+
+        Some(ShapedGlyph {
+            glyph: SectionGlyph {
+                glyph: Glyph {
+                    id,
+                    scale: self.glyph.glyph.scale,
+                    position: point(0.0, 0.0),
+                },
+                ..self.glyph.clone()
+            },
+            advance: font.as_scaled(self.glyph.glyph.scale).h_advance(id),
+            control: false,
+            whitespace: false,
+            inserted: true,
+            ..self.clone()
+        })
+    }
+}
+
+/// Concatenates the text of all sections, which glyph offsets index into.
+pub(crate) fn concat<S: ToSectionText>(sections: &[S]) -> String {
+    sections.iter().map(|s| s.to_section_text().text).collect()
+}
+
+/// Shapes all sections of the concatenated `bidi` text, one run of a single
+/// font, script & embedding level at a time, returning glyphs in logical
+/// order.
+///
+/// Fonts without a registered face fall back to a one to one character to
+/// glyph mapping.
+pub(crate) fn shape<F, S>(
+    fonts: &[F],
+    sections: &[S],
+    bidi: &BidiInfo<'_>,
+    fallback: &Fallback,
+    faces: &Faces,
+) -> Vec<ShapedGlyph>
+where
+    F: Font,
+    S: ToSectionText,
+{
+    let mut glyphs = Vec::new();
+    let mut parsed = HashMap::new();
+    let mut section_offset = 0;
+
+    for (section_index, section) in sections.iter().enumerate() {
+        let section = section.to_section_text();
+        let section_range = section_offset..section_offset + section.text.len();
+        section_offset = section_range.end;
+
+        if !valid_section(&section) {
+            continue;
+        }
+
+        let chain = fallback.chain(section_index, section.font_id);
+        let select =
+            |c, script_neutral, current| select_font(fonts, &chain, c, script_neutral, current);
+
+        for (range, script, level, font_id) in runs(bidi, section_range.clone(), select) {
+            let run = Run {
+                section_index,
+                section_offset: section_range.start,
+                section: &section,
+                range: range.start - section_range.start..range.end - section_range.start,
+                script,
+                level,
+                font_id,
+                vertical: false,
+            };
+
+            let font = &fonts[font_id];
+            let face = parsed.entry(font_id).or_insert_with(|| faces.face(font_id));
+
+            match face {
+                Some(face) => run.shape(font, face, &mut glyphs),
+                None => run.map_chars(font, &mut glyphs),
+            }
+        }
+    }
+
+    glyphs
+}
+
+/// Shapes all sections for top to bottom layout, one run of upright or
+/// sideways characters of a single font at a time, see `is_upright`. Upright
+/// runs are shaped vertically, with advances down the column & offsets from
+/// its center line to the glyph origin. Sideways runs are shaped
+/// horizontally, to be rotated.
+pub(crate) fn shape_vertical<F, S>(
+    fonts: &[F],
+    sections: &[S],
+    fallback: &Fallback,
+    faces: &Faces,
+) -> Vec<ShapedGlyph>
+where
+    F: Font,
+    S: ToSectionText,
+{
+    let mut glyphs = Vec::new();
+    let mut parsed = HashMap::new();
+    let mut section_offset = 0;
+
+    for (section_index, section) in sections.iter().enumerate() {
+        let section = section.to_section_text();
+        let section_start = section_offset;
+        section_offset += section.text.len();
+
+        if !valid_section(&section) {
+            continue;
+        }
+
+        let chain = fallback.chain(section_index, section.font_id);
+        let select =
+            |c, script_neutral, current| select_font(fonts, &chain, c, script_neutral, current);
+
+        for (range, upright, font_id) in orientation_runs(section.text, select) {
+            let run = Run {
+                section_index,
+                section_offset: section_start,
+                section: &section,
+                range,
+                script: Script::Common,
+                level: Level::ltr(),
+                font_id,
+                vertical: upright,
+            };
+
+            let font = &fonts[font_id];
+            let face = parsed.entry(font_id).or_insert_with(|| faces.face(font_id));
+
+            match face {
+                Some(face) => run.shape(font, face, &mut glyphs),
+                None => run.map_chars(font, &mut glyphs),
+            }
+        }
+    }
+
+    glyphs
+}
+
+/// Splits text into runs of upright or sideways characters of a single
+/// font. Characters extending a cluster, such as marks & variation
+/// selectors, join the run of their base character whatever their own
+/// orientation & font.
+fn orientation_runs<S>(text: &str, select_font: S) -> Vec<(Range<usize>, bool, FontId)>
+where
+    S: Fn(char, bool, Option<FontId>) -> FontId,
+{
+    let mut runs: Vec<(Range<usize>, bool, FontId)> = Vec::new();
+
+    for (index, c) in text.char_indices() {
+        let end = index + c.len_utf8();
+        if let Some((range, ..)) = runs.last_mut().filter(|_| extends_cluster(c)) {
+            range.end = end;
+            continue;
+        }
+
+        let upright = is_upright(c);
+        let script_neutral = matches!(c.script(), Script::Common | Script::Unknown);
+        let font_id = select_font(c, script_neutral, runs.last().map(|run| run.2));
+
+        match runs.last_mut() {
+            Some((range, run_upright, run_font))
+                if *run_font == font_id && *run_upright == upright =>
+            {
+                range.end = end
+            }
+            _ => runs.push((index..end, upright, font_id)),
+        }
+    }
+
+    runs
+}
+
+/// Returns whether the character extends the cluster of the one before it,
+/// as combining marks, variation selectors, joiners, emoji modifiers & tags
+/// do.
+fn extends_cluster(c: char) -> bool {
+    c.script() == Script::Inherited
+        || matches!(c, '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}')
+}
+
+/// Returns whether the cluster of the character at the byte index stays
+/// upright in vertical text, following its base character, see
+/// `is_upright`.
+pub(crate) fn is_upright_at(text: &str, index: usize) -> bool {
+    let mut chars = text[..index].chars().rev();
+    let c = match text[index..].chars().next() {
+        Some(c) => c,
+        None => return true,
+    };
+
+    match extends_cluster(c) {
+        true => chars.find(|c| !extends_cluster(*c)).is_some_and(is_upright),
+        false => is_upright(c),
+    }
+}
+
+/// Returns whether the character stays upright in vertical text, as CJK
+/// characters do, rather than being rotated sideways like Latin.
+pub(crate) fn is_upright(c: char) -> bool {
+    matches!(
+        c.script(),
+        Script::Han
+            | Script::Hiragana
+            | Script::Katakana
+            | Script::Hangul
+            | Script::Bopomofo
+            | Script::Yi
+    ) || matches!(
+        c,
+        '\u{2E80}'..='\u{2FFF}'
+            | '\u{3000}'..='\u{33FF}'
+            | '\u{FE10}'..='\u{FE1F}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF01}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE7}'
+            | '\u{1F000}'..='\u{1FAFF}'
+            | '\u{20000}'..='\u{3FFFF}'
+    )
+}
+
+/// A slice of a section's text of a single font, script & embedding level.
+struct Run<'a, 'b> {
+    section_index: usize,
+    section_offset: usize,
+    section: &'b SectionText<'a>,
+    range: Range<usize>,
+    script: Script,
+    level: Level,
+    font_id: FontId,
+    /// Shaped top to bottom, with upright glyphs.
+    vertical: bool,
+}
+
+impl Run<'_, '_> {
+    fn shape<F: Font>(&self, font: &F, face: &Face<'_>, out: &mut Vec<ShapedGlyph>) {
+        let text = self.section.text;
+        let scale_font = font.as_scaled(self.section.scale);
+        let (h_scale, v_scale) = (scale_font.h_scale_factor(), scale_font.v_scale_factor());
+
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(&text[self.range.clone()]);
+        buffer.set_pre_context(&text[..self.range.start]);
+        buffer.set_post_context(&text[self.range.end..]);
+        buffer.set_direction(if self.vertical {
+            Direction::TopToBottom
+        } else if self.level.is_rtl() {
+            Direction::RightToLeft
+        } else {
+            Direction::LeftToRight
+        });
+        if let Some(script) = hb_script(self.script) {
+            buffer.set_script(script);
+        }
+        buffer.guess_segment_properties();
+
+        let output = rustybuzz::shape(face, &[], buffer);
+
+        let start = out.len();
+        for (info, position) in output.glyph_infos().iter().zip(output.glyph_positions()) {
+            let glyph = Glyph {
+                id: GlyphId(info.glyph_id as u16),
+                scale: self.section.scale,
+                position: point(
+                    position.x_offset as f32 * h_scale,
+                    -position.y_offset as f32 * v_scale,
+                ),
+            };
+
+            let advance = if self.vertical {
+                // vertical advances point up the y axis of font units
+                -position.y_advance as f32 * v_scale
+            } else {
+                // keep advances the font adds to its data's, e.g. synthetic emboldening
+                let extra_advance = face
+                    .glyph_hor_advance(rustybuzz::ttf_parser::GlyphId(glyph.id.0))
+                    .map_or(0.0, |data_advance| {
+                        font.h_advance_unscaled(glyph.id) - f32::from(data_advance)
+                    });
+                (position.x_advance as f32 + extra_advance) * h_scale
+            };
+
+            out.push(self.glyph(
+                font,
+                glyph,
+                advance,
+                self.range.start + info.cluster as usize,
+            ));
+        }
+
+        // the shaper outputs right-to-left runs in visual order
+        if self.level.is_rtl() {
+            out[start..].reverse();
+        }
+    }
+
+    fn map_chars<F: Font>(&self, font: &F, out: &mut Vec<ShapedGlyph>) {
+        let scale_font = font.as_scaled(self.section.scale);
+        let mut last_glyph_id = None;
+
+        for (index, c) in self.section.text[self.range.clone()].char_indices() {
+            let mut glyph = scale_font.scaled_glyph(c);
+
+            if self.vertical {
+                // centered on the column, advancing by the line height
+                glyph.position = point(-scale_font.h_advance(glyph.id) / 2.0, scale_font.ascent());
+                let advance = scale_font.height();
+                out.push(self.glyph(font, glyph, advance, self.range.start + index));
+                continue;
+            }
+
+            if let (Some(last), Some(prev)) = (last_glyph_id, out.last_mut()) {
+                prev.advance += scale_font.kern(last, glyph.id);
+            }
+            last_glyph_id = Some(glyph.id);
+
+            let advance = scale_font.h_advance(glyph.id);
+            out.push(self.glyph(font, glyph, advance, self.range.start + index));
+        }
+    }
+
+    fn glyph<F: Font>(
+        &self,
+        font: &F,
+        mut glyph: Glyph,
+        advance: f32,
+        byte_index: usize,
+    ) -> ShapedGlyph {
+        let c = self.section.text[byte_index..]
+            .chars()
+            .next()
+            .unwrap_or_default();
+        // soft hyphens are only drawn where lines break at them
+        let control = c.is_control() || c == SOFT_HYPHEN;
+        if control {
+            glyph.position = point(0.0, 0.0);
+        }
+
+        ShapedGlyph {
+            glyph: SectionGlyph {
+                section_index: self.section_index,
+                byte_index,
+                glyph,
+                font_id: self.font_id,
+            },
+            advance: if control { 0.0 } else { advance },
+            offset: self.section_offset + byte_index,
+            level: self.level.number(),
+            v_metrics: font.as_scaled(self.section.scale).into(),
+            control,
+            whitespace: c.is_whitespace(),
+            inserted: false,
+        }
+    }
+}
+
+type RunInfo = (Range<usize>, Script, Level, FontId);
+
+/// Splits a range of text into runs of a single font, script & embedding
+/// level, common & inherited characters join the run they are found in.
+fn runs<S>(bidi: &BidiInfo<'_>, range: Range<usize>, select_font: S) -> Vec<RunInfo>
+where
+    S: Fn(char, bool, Option<FontId>) -> FontId,
+{
+    let mut runs: Vec<RunInfo> = Vec::new();
+
+    for (index, c) in bidi.text[range.clone()].char_indices() {
+        let index = range.start + index;
+        let end = index + c.len_utf8();
+        let level = bidi.levels[index];
+        let script = match c.script() {
+            Script::Common | Script::Inherited | Script::Unknown => None,
+            script => Some(script),
+        };
+        let font_id = select_font(c, script.is_none(), runs.last().map(|run| run.3));
+
+        match (runs.last_mut(), script) {
+            (Some((range, _, run_level, run_font)), None)
+                if *run_level == level && *run_font == font_id =>
+            {
+                range.end = end
+            }
+            (Some((range, run_script, run_level, run_font)), Some(script))
+                if *run_level == level
+                    && *run_font == font_id
+                    && (*run_script == script || is_neutral(*run_script)) =>
+            {
+                range.end = end;
+                *run_script = script;
+            }
+            _ => runs.push((index..end, script.unwrap_or(Script::Common), level, font_id)),
+        }
+    }
+
+    runs
+}
+
+#[inline]
+fn is_neutral(script: Script) -> bool {
+    script == Script::Common
+}
+
+#[inline]
+fn hb_script(script: Script) -> Option<rustybuzz::Script> {
+    if is_neutral(script) {
+        return None;
+    }
+    rustybuzz::Script::from_iso15924_tag(Tag::from_bytes_lossy(script.short_name().as_bytes()))
+}
+
+#[inline]
+fn valid_section(s: &SectionText<'_>) -> bool {
+    let PxScale { x, y } = s.scale;
+    x > 0.0 && y > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the orientation runs of the text, all in one font.
+    fn runs(text: &str) -> Vec<(&str, bool)> {
+        orientation_runs(text, |_, _, _| FontId(0))
+            .into_iter()
+            .map(|(range, upright, _)| (&text[range], upright))
+            .collect()
+    }
+
+    #[test]
+    fn splits_upright_and_sideways_runs() {
+        assert_eq!(
+            runs("縦書きtext。"),
+            [("縦書き", true), ("text", false), ("。", true)]
+        );
+    }
+
+    #[test]
+    fn keeps_clusters_in_the_run_of_their_base() {
+        // variation selector & combining dakuten after upright bases
+        assert_eq!(
+            runs("葛\u{E0100}か\u{3099}a"),
+            [("葛\u{E0100}か\u{3099}", true), ("a", false)]
+        );
+        // combining acute after a sideways base
+        assert_eq!(runs("e\u{301}字"), [("e\u{301}", false), ("字", true)]);
+    }
+
+    #[test]
+    fn keeps_clusters_in_the_font_of_their_base() {
+        let text = "字\u{FE00}";
+        let runs = orientation_runs(text, |c, _, _| match c {
+            '字' => FontId(1),
+            _ => FontId(0),
+        });
+        assert_eq!(runs, [(0..text.len(), true, FontId(1))]);
+    }
+
+    #[test]
+    fn orients_clusters_by_their_base() {
+        let text = "字\u{FE00}e\u{301}";
+        assert!(is_upright_at(text, 0));
+        assert!(is_upright_at(text, "字".len()));
+        assert!(!is_upright_at(text, "字\u{FE00}".len()));
+        assert!(!is_upright_at(text, "字\u{FE00}e".len()));
+    }
+}
@@ -0,0 +1,116 @@
+use super::shaper::ShapedGlyph;
+use std::hash::{Hash, Hasher};
+
+/// Tracking & leading of text laid out by a
+/// [`ShapedLayout`](struct.ShapedLayout.html).
+#[derive(Debug, Clone, Copy)]
+pub struct Spacing {
+    /// Space added after each character, in ems of the section scale.
+    pub letter: f32,
+    /// Space added to each whitespace character, in ems of the section scale.
+    pub word: f32,
+    /// Multiplier of the font line height, extra leading being split evenly
+    /// above & below the line.
+    pub line_height: f32,
+}
+
+impl Default for Spacing {
+    #[inline]
+    fn default() -> Self {
+        Spacing {
+            letter: 0.0,
+            word: 0.0,
+            line_height: 1.0,
+        }
+    }
+}
+
+impl Spacing {
+    /// Returns an identical `Spacing` with a new letter spacing.
+    #[inline]
+    pub fn letter(self, letter: f32) -> Self {
+        Spacing { letter, ..self }
+    }
+
+    /// Returns an identical `Spacing` with a new word spacing.
+    #[inline]
+    pub fn word(self, word: f32) -> Self {
+        Spacing { word, ..self }
+    }
+
+    /// Returns an identical `Spacing` with a new line height multiplier.
+    #[inline]
+    pub fn line_height(self, line_height: f32) -> Self {
+        Spacing {
+            line_height,
+            ..self
+        }
+    }
+}
+
+impl PartialEq for Spacing {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.letter.to_bits() == other.letter.to_bits()
+            && self.word.to_bits() == other.word.to_bits()
+            && self.line_height.to_bits() == other.line_height.to_bits()
+    }
+}
+
+impl Eq for Spacing {}
+
+impl Hash for Spacing {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.letter.to_bits().hash(state);
+        self.word.to_bits().hash(state);
+        self.line_height.to_bits().hash(state);
+    }
+}
+
+/// Spacing of all sections, with per section overrides.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub(crate) struct Spacings {
+    pub spacing: Spacing,
+    /// Per section overrides of `spacing`, by section index.
+    pub sections: Vec<(usize, Spacing)>,
+}
+
+impl Spacings {
+    fn get(&self, section_index: usize) -> Spacing {
+        self.sections
+            .iter()
+            .find(|(index, _)| *index == section_index)
+            .map_or(self.spacing, |(_, spacing)| *spacing)
+    }
+
+    /// Grows glyph advances & vertical metrics, glyphs being in logical order.
+    pub fn apply(&self, glyphs: &mut [ShapedGlyph]) {
+        if *self == Self::default() {
+            return;
+        }
+
+        for index in 0..glyphs.len() {
+            // letter spacing follows whole clusters, keeping ligatures & marks together
+            let cluster_end =
+                glyphs.get(index + 1).map(|next| next.offset) != Some(glyphs[index].offset);
+
+            let glyph = &mut glyphs[index];
+            let spacing = self.get(glyph.glyph.section_index);
+            let em = glyph.glyph.glyph.scale.x;
+
+            if !glyph.control {
+                if cluster_end {
+                    glyph.advance += spacing.letter * em;
+                }
+                if glyph.whitespace {
+                    glyph.advance += spacing.word * em;
+                }
+            }
+
+            let leading = (spacing.line_height - 1.0) * glyph.v_metrics.height();
+            glyph.v_metrics.ascent += leading / 2.0;
+            glyph.v_metrics.descent -= leading / 2.0;
+        }
+    }
+}
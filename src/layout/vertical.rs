@@ -0,0 +1,328 @@
+use super::{
+    faces::Faces,
+    fallback::Fallback,
+    lines::lines,
+    overflow::Overflow,
+    rotate::rotated_about_center,
+    shaper::{concat, is_upright_at, shape_vertical},
+    spacing::{Spacing, Spacings},
+    words::words,
+};
+use crate::{
+    ab_glyph::{point, Font, Rect, ScaleFont},
+    FontFace, Variation,
+};
+use glyph_brush::{
+    BuiltInLineBreaker, FontId, GlyphPositioner, HorizontalAlign, Layout, LineBreaker,
+    SectionGeometry, SectionGlyph, ToSectionText, VerticalAlign,
+};
+use std::f32::consts::FRAC_PI_2;
+
+/// A [`GlyphPositioner`](trait.GlyphPositioner.html) laying text out in top
+/// to bottom columns, progressing right to left, as in vertical Japanese.
+///
+/// CJK characters stay upright and are shaped vertically, using the font's
+/// vertical metrics & alternate forms (`vert`) where it has them. Other runs,
+/// such as Latin, are laid out sideways & must be rotated a quarter turn
+/// clockwise, which [`GlyphBrush::queue_vertical`](struct.GlyphBrush.html#method.queue_vertical)
+/// does through [`Instance::rotation`](struct.Instance.html#structfield.rotation).
+///
+/// Columns wrap at the bounds height, the horizontal alignment positions the
+/// block of columns & the vertical alignment each column, matching
+/// [`bounds_rect`](trait.GlyphPositioner.html#tymethod.bounds_rect).
+///
+/// Font fallback, faces, variations, spacing & overflow are set as on a
+/// [`ShapedLayout`](struct.ShapedLayout.html), letter spacing following the
+/// column & line height widening it.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct VerticalLayout<L: LineBreaker = BuiltInLineBreaker> {
+    layout: Layout<L>,
+    fallback: Fallback,
+    faces: Faces,
+    spacing: Spacings,
+    overflow: Overflow,
+}
+
+impl Default for VerticalLayout {
+    #[inline]
+    fn default() -> Self {
+        VerticalLayout::default_wrap()
+    }
+}
+
+impl VerticalLayout {
+    /// A single column, right aligned.
+    #[inline]
+    pub fn default_single_column() -> Self {
+        Layout::default_single_line()
+            .h_align(HorizontalAlign::Right)
+            .into()
+    }
+
+    /// Columns wrapping at the bounds height, right aligned.
+    #[inline]
+    pub fn default_wrap() -> Self {
+        Layout::default_wrap()
+            .h_align(HorizontalAlign::Right)
+            .into()
+    }
+}
+
+impl<L: LineBreaker> From<Layout<L>> for VerticalLayout<L> {
+    #[inline]
+    fn from(layout: Layout<L>) -> Self {
+        VerticalLayout {
+            layout,
+            fallback: Fallback::default(),
+            faces: Faces::default(),
+            spacing: Spacings::default(),
+            overflow: Overflow::default(),
+        }
+    }
+}
+
+impl<L: LineBreaker> VerticalLayout<L> {
+    /// Returns an identical `VerticalLayout` with a new horizontal alignment
+    /// of the block of columns.
+    pub fn h_align(self, h_align: HorizontalAlign) -> Self {
+        VerticalLayout {
+            layout: self.layout.h_align(h_align),
+            ..self
+        }
+    }
+
+    /// Returns an identical `VerticalLayout` with a new vertical alignment of
+    /// each column.
+    pub fn v_align(self, v_align: VerticalAlign) -> Self {
+        VerticalLayout {
+            layout: self.layout.v_align(v_align),
+            ..self
+        }
+    }
+
+    /// Returns an identical `VerticalLayout` with a new line breaker, e.g. a
+    /// [`KinsokuLineBreaker`](struct.KinsokuLineBreaker.html).
+    pub fn line_breaker<L2: LineBreaker>(self, line_breaker: L2) -> VerticalLayout<L2> {
+        VerticalLayout {
+            layout: self.layout.line_breaker(line_breaker),
+            fallback: self.fallback,
+            faces: self.faces,
+            spacing: self.spacing,
+            overflow: self.overflow,
+        }
+    }
+
+    /// Returns an identical `VerticalLayout` with a new font fallback chain,
+    /// see [`ShapedLayout::fallback_fonts`](struct.ShapedLayout.html#method.fallback_fonts).
+    pub fn fallback_fonts(mut self, fonts: Vec<FontId>) -> Self {
+        self.fallback.fonts = fonts;
+        self
+    }
+
+    /// Returns an identical `VerticalLayout` with a font fallback chain used
+    /// for the section `Text` at `section_index` instead of the
+    /// [default one](#method.fallback_fonts).
+    pub fn section_fallback_fonts(mut self, section_index: usize, fonts: Vec<FontId>) -> Self {
+        self.fallback
+            .sections
+            .retain(|(index, _)| *index != section_index);
+        self.fallback.sections.push((section_index, fonts));
+        self
+    }
+
+    /// Returns an identical `VerticalLayout` with new letter spacing, added
+    /// down the column, word spacing & line height, multiplying the column
+    /// width.
+    ///
+    /// Defaults to the font's own metrics, see
+    /// [`Spacing::default`](struct.Spacing.html).
+    pub fn spacing(mut self, spacing: Spacing) -> Self {
+        self.spacing.spacing = spacing;
+        self
+    }
+
+    /// Returns an identical `VerticalLayout` with spacing used for the
+    /// section `Text` at `section_index` instead of the
+    /// [default one](#method.spacing).
+    pub fn section_spacing(mut self, section_index: usize, spacing: Spacing) -> Self {
+        self.spacing
+            .sections
+            .retain(|(index, _)| *index != section_index);
+        self.spacing.sections.push((section_index, spacing));
+        self
+    }
+
+    /// Returns an identical `VerticalLayout` shaping the font `font_id` with
+    /// the face, see
+    /// [`ShapedLayout::font_face`](struct.ShapedLayout.html#method.font_face).
+    pub fn font_face(mut self, font_id: FontId, face: FontFace) -> Self {
+        self.faces.insert(font_id, face);
+        self
+    }
+
+    /// Returns an identical `VerticalLayout` shaping the variable font
+    /// `font_id` at the axis coordinates, see
+    /// [`ShapedLayout::font_variations`](struct.ShapedLayout.html#method.font_variations).
+    pub fn font_variations(mut self, font_id: FontId, variations: Vec<Variation>) -> Self {
+        self.faces.insert_variations(font_id, variations);
+        self
+    }
+
+    /// Returns an identical `VerticalLayout` with the faces & variation
+    /// coordinates of fonts it has none set for.
+    pub(crate) fn with_faces(mut self, faces: &Faces) -> Self {
+        self.faces.extend(faces);
+        self
+    }
+
+    /// Returns an identical `VerticalLayout` with a new way of handling text
+    /// overflowing the bounds height, ellipses ending or splitting columns.
+    ///
+    /// Columns don't fade, [`Overflow::Fade`](enum.Overflow.html#variant.Fade)
+    /// clips them like [`Overflow::Clip`](enum.Overflow.html#variant.Clip),
+    /// the default.
+    pub fn overflow(self, overflow: Overflow) -> Self {
+        VerticalLayout { overflow, ..self }
+    }
+
+    /// Returns the clockwise rotation in radians of the glyph of the
+    /// character at the byte index, a quarter turn for sideways clusters.
+    #[inline]
+    pub(crate) fn rotation(text: &str, index: usize) -> f32 {
+        match is_upright_at(text, index) {
+            true => 0.0,
+            false => FRAC_PI_2,
+        }
+    }
+}
+
+impl<L: LineBreaker> GlyphPositioner for VerticalLayout<L> {
+    fn calculate_glyphs<F, S>(
+        &self,
+        fonts: &[F],
+        geometry: &SectionGeometry,
+        sections: &[S],
+    ) -> Vec<SectionGlyph>
+    where
+        F: Font,
+        S: ToSectionText,
+    {
+        let SectionGeometry {
+            screen_position: (screen_x, screen_y),
+            bounds: (bound_w, bound_h),
+        } = *geometry;
+        // columns run along the bounds height
+        let column_geometry = SectionGeometry {
+            screen_position: (screen_y, screen_x),
+            bounds: (bound_h, bound_w),
+        };
+
+        let (line_breaker, h_align, v_align, single_column) = match self.layout {
+            Layout::SingleLine {
+                line_breaker,
+                h_align,
+                v_align,
+            } => (line_breaker, h_align, v_align, true),
+            Layout::Wrap {
+                line_breaker,
+                h_align,
+                v_align,
+            } => (line_breaker, h_align, v_align, false),
+        };
+
+        let text = concat(sections);
+        let mut glyphs = shape_vertical(fonts, sections, &self.fallback, &self.faces);
+        self.spacing.apply(&mut glyphs);
+        let height_bound = match single_column {
+            true => f32::INFINITY,
+            false => bound_h,
+        };
+        let mut columns = lines(
+            words(fonts, glyphs, &text, &line_breaker, false),
+            height_bound,
+            text.len(),
+        );
+        if single_column {
+            columns.truncate(1);
+        }
+        self.overflow
+            .apply(fonts, &column_geometry, !single_column, &mut columns);
+
+        // keep the columns fitting the bounds width, at least one
+        let mut width = 0.0;
+        let fitting = columns
+            .iter()
+            .take_while(|column| {
+                width += column.line_height();
+                width <= bound_w * (1.0 + f32::EPSILON)
+            })
+            .count()
+            .max(1);
+        columns.truncate(fitting);
+
+        let total_width: f32 = columns.iter().map(|column| column.line_height()).sum();
+        let mut right = match h_align {
+            HorizontalAlign::Left => screen_x + total_width,
+            HorizontalAlign::Center => screen_x + total_width / 2.0,
+            HorizontalAlign::Right => screen_x,
+        };
+
+        let mut out = vec![];
+        for column in columns {
+            let column_width = column.line_height();
+            let center = right - column_width / 2.0;
+            let mut caret = match v_align {
+                VerticalAlign::Top => screen_y,
+                VerticalAlign::Center => screen_y - column.rightmost / 2.0,
+                VerticalAlign::Bottom => screen_y - column.rightmost,
+            };
+
+            for shaped in column.glyphs {
+                let mut sg = shaped.glyph;
+                let pen = caret;
+                caret += shaped.advance;
+                if shaped.control {
+                    continue;
+                }
+
+                let section_text = sections[sg.section_index].to_section_text().text;
+                let rotation = Self::rotation(section_text, sg.byte_index);
+                let offset = sg.glyph.position;
+
+                sg.glyph = match rotation {
+                    // inserted glyphs, such as an ellipsis, are shaped
+                    // horizontally, center them on the column
+                    0.0 if shaped.inserted => {
+                        let mut glyph = sg.glyph;
+                        let ascent = fonts[sg.font_id].as_scaled(glyph.scale).ascent();
+                        glyph.position = point(center - shaped.advance / 2.0, pen + ascent);
+                        glyph
+                    }
+                    0.0 => {
+                        let mut glyph = sg.glyph;
+                        glyph.position = point(center, pen) + offset;
+                        glyph
+                    }
+                    _ => {
+                        // the baseline runs down the column, centering the
+                        // ascent & descent on it
+                        let v_metrics = shaped.v_metrics;
+                        let baseline = center - (v_metrics.ascent + v_metrics.descent) / 2.0;
+                        let origin = point(baseline - offset.y, pen + offset.x);
+                        rotated_about_center(&fonts[sg.font_id], sg.glyph, origin, rotation)
+                    }
+                };
+                out.push(sg);
+            }
+
+            right -= column_width;
+        }
+
+        out
+    }
+
+    #[inline]
+    fn bounds_rect(&self, geometry: &SectionGeometry) -> Rect {
+        self.layout.bounds_rect(geometry)
+    }
+}
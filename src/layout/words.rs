@@ -0,0 +1,182 @@
+use super::shaper::{ShapedGlyph, SOFT_HYPHEN};
+use crate::ab_glyph::{Font, PxScaleFont, ScaleFont};
+use glyph_brush::{LineBreak, LineBreaker};
+use unicode_script::{Script, UnicodeScript};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct VMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub line_gap: f32,
+}
+
+impl VMetrics {
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.ascent - self.descent + self.line_gap
+    }
+
+    #[inline]
+    pub fn max(self, other: Self) -> Self {
+        if other.height() > self.height() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<F: Font> From<PxScaleFont<F>> for VMetrics {
+    #[inline]
+    fn from(scale_font: PxScaleFont<F>) -> Self {
+        Self {
+            ascent: scale_font.ascent(),
+            descent: scale_font.descent(),
+            line_gap: scale_font.line_gap(),
+        }
+    }
+}
+
+/// Glyphs between two line break opportunities.
+pub(crate) struct Word {
+    pub glyphs: Vec<ShapedGlyph>,
+    pub layout_width: f32,
+    pub layout_width_no_trail: f32,
+    pub max_v_metrics: VMetrics,
+    pub hard_break: bool,
+    /// Hyphen ending the word's line if it is wrapped after the word, for
+    /// words broken within.
+    pub hyphen: Option<ShapedGlyph>,
+}
+
+impl Word {
+    fn new() -> Self {
+        Word {
+            glyphs: Vec::new(),
+            layout_width: 0.0,
+            layout_width_no_trail: 0.0,
+            max_v_metrics: VMetrics::default(),
+            hard_break: false,
+            hyphen: None,
+        }
+    }
+
+    fn push(&mut self, glyph: ShapedGlyph, hangs: bool) {
+        self.max_v_metrics = self.max_v_metrics.max(glyph.v_metrics);
+        self.layout_width += glyph.advance;
+        if !glyph.whitespace && !hangs {
+            // not an invisible or hanging trail
+            self.layout_width_no_trail = self.layout_width;
+        }
+        self.glyphs.push(glyph);
+    }
+}
+
+/// Splits shaped glyphs into words at the line breaks found in the
+/// concatenated section `text`.
+///
+/// Words ending at soft hyphens or within runs of letters of hyphenated
+/// scripts, e.g. at hyphenation points, get a hyphen in the font of their
+/// last glyph.
+///
+/// Trailing full stops & commas of words are excluded from their wrapping
+/// width when `hanging_punctuation` is set.
+pub(crate) fn words<F: Font, L: LineBreaker>(
+    fonts: &[F],
+    glyphs: Vec<ShapedGlyph>,
+    text: &str,
+    line_breaker: &L,
+    hanging_punctuation: bool,
+) -> Vec<Word> {
+    let mut line_breaks = line_breaker.line_breaks(text).peekable();
+    let mut words = Vec::new();
+    let mut word = Word::new();
+
+    for glyph in glyphs {
+        let mut broken = None;
+        while let Some(line_break) = line_breaks.next_if(|lb| lb.offset() <= glyph.offset) {
+            broken = Some(line_break);
+        }
+
+        if let Some(line_break) = broken {
+            if !word.glyphs.is_empty() {
+                word.hard_break = matches!(line_break, LineBreak::Hard(_));
+                if !word.hard_break && is_hyphenated(text, line_break.offset()) {
+                    word.hyphen = word.glyphs.last().and_then(|last| {
+                        last.with_char(fonts, '\u{2010}')
+                            .or_else(|| last.with_char(fonts, '-'))
+                    });
+                }
+                words.push(std::mem::replace(&mut word, Word::new()));
+            }
+        }
+
+        let hangs = hanging_punctuation
+            && text[glyph.offset..]
+                .chars()
+                .next()
+                .is_some_and(is_hanging_punctuation);
+        word.push(glyph, hangs);
+    }
+
+    if !word.glyphs.is_empty() {
+        // simulate hard-break at end of all sections
+        word.hard_break = true;
+        words.push(word);
+    }
+
+    words
+}
+
+/// Returns whether breaking the text at `offset` splits a word, needing a
+/// hyphen.
+fn is_hyphenated(text: &str, offset: usize) -> bool {
+    let before = text[..offset].chars().next_back();
+    let after = text[offset..].chars().next();
+
+    match (before, after) {
+        (Some(SOFT_HYPHEN), _) => true,
+        // e.g. not between Latin & Han, which break without hyphens
+        (Some(before), Some(after)) => [before, after]
+            .iter()
+            .all(|c| c.is_alphabetic() && hyphenates(c.script())),
+        _ => false,
+    }
+}
+
+/// Full stops & commas which may hang past the end of lines.
+#[inline]
+fn is_hanging_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        ',' | '.'
+            | '\u{060C}'
+            | '\u{06D4}'
+            | '、'
+            | '。'
+            | '，'
+            | '．'
+            | '﹐'
+            | '﹑'
+            | '﹒'
+            | '｡'
+            | '､'
+    )
+}
+
+/// Returns whether words of the script are hyphenated when broken, rather
+/// than broken between any characters.
+#[inline]
+fn hyphenates(script: Script) -> bool {
+    !matches!(
+        script,
+        Script::Han
+            | Script::Hiragana
+            | Script::Katakana
+            | Script::Hangul
+            | Script::Thai
+            | Script::Lao
+            | Script::Khmer
+            | Script::Myanmar
+    )
+}
@@ -6,8 +6,21 @@
 //! [`glyph_brush`]: https://github.com/alexheretic/glyph-brush/tree/master/glyph-brush
 #![deny(unused_results)]
 mod builder;
+mod effect;
+mod extra;
+mod fit;
+mod font;
+mod label;
+mod layout;
+mod line_breaker;
+mod particles;
+mod path;
 mod pipeline;
 mod region;
+mod retained;
+mod reveal;
+mod stamp;
+mod transform;
 
 pub use region::Region;
 
@@ -17,28 +30,55 @@ use luminance::{
     pipeline::PipelineError,
     pipeline::{Pipeline as LuminancePipeline, TextureBinding},
     pixel::NormR8UI,
-    pixel::NormUnsigned,
+    pixel::{Floating, NormUnsigned, RGBA32F},
     shader::types::Mat44,
     shading_gate::ShadingGate,
     tess::Interleaved,
     texture::Dim2,
 };
 
-use pipeline::Pipeline;
+use extra::{extend_section, GlyphExtra};
+use fit::FitCache;
+use font::SyntheticFont;
+use label::Occupancy;
+use layout::Faces;
+use path::place_on_path;
+use pipeline::{Pipeline, StampInstance, StampVertex, TaggedInstance};
+use retained::{RetainedText, RetainedTexts};
+use transform::TransformQueue;
 
 pub use builder::GlyphBrushBuilder;
+pub use effect::Effect;
+pub use font::{
+    FontFace, FontFamilies, FontFamily, FontMatch, FontStyle, FontVariant, FontWeight,
+    LoadFontError, Synthesis, SystemFont, SystemFonts, Variation,
+};
 pub use glyph_brush::ab_glyph;
 pub use glyph_brush::{
     BuiltInLineBreaker, Extra, FontId, GlyphCruncher, GlyphPositioner, GlyphVertex,
     HorizontalAlign, Layout, LineBreak, LineBreaker, Section, SectionGeometry, SectionGlyph,
     SectionGlyphIter, SectionText, Text, VerticalAlign,
 };
-pub use pipeline::{Instance, LeftTop, RightBottom, TexLeftTop, TexRightBottom, VertexColor};
+pub use label::{Label, LabelPlacer, LabelPosition};
+pub use layout::{
+    BidiLine, BidiMap, Overflow, ShapedLayout, Spacing, TextDirection, VerticalLayout,
+};
+pub use line_breaker::{HyphenationLineBreaker, KinsokuLineBreaker, Language};
+pub use particles::{Easing, ParticleStyle, TextParticles};
+pub use path::TextPath;
+pub use pipeline::{
+    Baseline, BillboardMode, EffectId, EffectParams, Fade, Instance, LeftTop, LocalTransform,
+    Pivot, RightBottom, Rotation, Skew, TexLeftTop, TexRightBottom, TransformIndex, VertexColor,
+};
+pub use retained::TextHandle;
+pub use reveal::Reveal;
+pub use stamp::Stamp;
+pub use transform::{Billboard, SectionTransform};
 
-use ab_glyph::{Font, FontArc, Rect};
+use ab_glyph::{Font, FontArc, InvalidFont, Rect};
 
 use core::hash::BuildHasher;
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
 use glyph_brush::{BrushAction, BrushError, DefaultSectionHasher};
 use log::{log_enabled, warn};
@@ -48,14 +88,31 @@ pub trait GlyphBrushBackend:
     + backend::texture::Texture<Dim2, NormR8UI>
     + backend::shader::Shader
     + for<'a> backend::shader::Uniformable<'a, Mat44<f32>, Target = Mat44<f32>>
+    + for<'a> backend::shader::Uniformable<'a, f32, Target = f32>
     + for<'a> backend::shader::Uniformable<
         'a,
         TextureBinding<Dim2, NormUnsigned>,
         Target = TextureBinding<Dim2, NormUnsigned>,
+    > + backend::pipeline::PipelineTexture<Dim2, RGBA32F>
+    + backend::texture::Texture<Dim2, RGBA32F>
+    + for<'a> backend::shader::Uniformable<
+        'a,
+        TextureBinding<Dim2, Floating>,
+        Target = TextureBinding<Dim2, Floating>,
     > + backend::tess::Tess<(), u32, Instance, Interleaved>
-    + backend::pipeline::PipelineBase
+    + for<'a> backend::tess::InstanceSlice<'a, (), u32, Instance, Interleaved, Instance>
+    + backend::tess::Tess<StampVertex, u32, StampInstance, Interleaved>
+    + for<'a> backend::tess::InstanceSlice<
+        'a,
+        StampVertex,
+        u32,
+        StampInstance,
+        Interleaved,
+        StampInstance,
+    > + backend::pipeline::PipelineBase
     + backend::render_gate::RenderGate
     + backend::tess_gate::TessGate<(), u32, Instance, Interleaved>
+    + backend::tess_gate::TessGate<StampVertex, u32, StampInstance, Interleaved>
 {
 }
 
@@ -64,14 +121,31 @@ impl<B: ?Sized> GlyphBrushBackend for B where
         + backend::texture::Texture<Dim2, NormR8UI>
         + backend::shader::Shader
         + for<'a> backend::shader::Uniformable<'a, Mat44<f32>, Target = Mat44<f32>>
+        + for<'a> backend::shader::Uniformable<'a, f32, Target = f32>
         + for<'a> backend::shader::Uniformable<
             'a,
             TextureBinding<Dim2, NormUnsigned>,
             Target = TextureBinding<Dim2, NormUnsigned>,
+        > + backend::pipeline::PipelineTexture<Dim2, RGBA32F>
+        + backend::texture::Texture<Dim2, RGBA32F>
+        + for<'a> backend::shader::Uniformable<
+            'a,
+            TextureBinding<Dim2, Floating>,
+            Target = TextureBinding<Dim2, Floating>,
         > + backend::tess::Tess<(), u32, Instance, Interleaved>
-        + backend::pipeline::PipelineBase
+        + for<'a> backend::tess::InstanceSlice<'a, (), u32, Instance, Interleaved, Instance>
+        + backend::tess::Tess<StampVertex, u32, StampInstance, Interleaved>
+        + for<'a> backend::tess::InstanceSlice<
+            'a,
+            StampVertex,
+            u32,
+            StampInstance,
+            Interleaved,
+            StampInstance,
+        > + backend::pipeline::PipelineBase
         + backend::render_gate::RenderGate
         + backend::tess_gate::TessGate<(), u32, Instance, Interleaved>
+        + backend::tess_gate::TessGate<StampVertex, u32, StampInstance, Interleaved>
 {
 }
 
@@ -84,7 +158,20 @@ where
     B: GlyphBrushBackend,
 {
     pipeline: Pipeline<B>,
-    glyph_brush: glyph_brush::GlyphBrush<Instance, Extra, F, H>,
+    glyph_brush: glyph_brush::GlyphBrush<TaggedInstance, GlyphExtra, F, H>,
+    shaping: Option<ShapedLayout>,
+    families: FontFamilies,
+    variations: HashMap<(FontId, Vec<Variation>), FontId>,
+    /// Faces to shape with, set on the layouts queued through the brush.
+    faces: Faces,
+    synthetic: HashMap<(FontId, Synthesis), FontId>,
+    skews: HashMap<FontId, f32>,
+    fit_cache: FitCache,
+    transforms: TransformQueue,
+    layer: u32,
+    retained: RetainedTexts,
+    /// Copies of the sections stamped this frame, with their positions.
+    stamps: Vec<(Vec<Stamp>, (f32, f32))>,
 }
 
 impl<B, F: Font, H: BuildHasher> GlyphBrush<B, F, H>
@@ -101,7 +188,365 @@ where
     where
         S: Into<Cow<'a, Section<'a>>>,
     {
-        self.glyph_brush.queue(section)
+        let section = self.extend_section(&section.into(), self.default_fade());
+        self.queue_extended(section)
+    }
+
+    /// Queues a section/layout to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued) with its
+    /// own 4x4 transform, applied to its pixel coordinates before the draw
+    /// call's transform, e.g. placing a label in world space.
+    ///
+    /// Sections with distinct transforms are drawn together, without
+    /// [`draw_queued_with_transform`](#method.draw_queued_with_transform)
+    /// calls per transform. Transforms are column-major, as the draw call's,
+    /// and are uploaded to a texture indexed by
+    /// [`Instance::transform_index`](struct.Instance.html#structfield.transform_index)
+    /// so changing them does not lay sections out again. Up to 262,143
+    /// distinct transforms a frame fit the texture size every GL 3.3
+    /// implementation supports.
+    pub fn queue_with_transform<'a, S>(&mut self, section: S, transform: [f32; 16])
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let mut section = self.extend_section(&section.into(), self.default_fade());
+        let transform_index = self.transforms.index(transform);
+        for text in &mut section.text {
+            text.extra.transform_index = transform_index;
+        }
+        self.queue_extended(section)
+    }
+
+    /// Queues a section/layout anchored at a 3D world position, to be drawn
+    /// by the next call of
+    /// [`draw_queued_3d`](struct.GlyphBrush.html#method.draw_queued_3d) with
+    /// the camera's view & projection, e.g. labelling objects of a scene.
+    ///
+    /// The [`Billboard`](enum.Billboard.html) mode fixes the label's
+    /// orientation in the world, or turns it to face the camera, optionally
+    /// at a constant size on screen. Glyphs are depth tested against the
+    /// scene at their world-space depth.
+    pub fn queue_world<'a, S>(&mut self, section: S, anchor: [f32; 3], billboard: Billboard)
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let mut section = self.extend_section(&section.into(), self.default_fade());
+        let transform_index = self.transforms.index(billboard.model(anchor));
+        for text in &mut section.text {
+            text.extra.transform_index = transform_index;
+            text.extra.billboard = billboard.mode();
+        }
+        self.queue_extended(section)
+    }
+
+    /// Queues the labels not overlapping others of higher priority, to be
+    /// drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), e.g.
+    /// points of interest on a map. Returns the indices of the labels queued.
+    ///
+    /// Label anchors are projected to pixels of a `target_width` x
+    /// `target_height` target by the column-major `view_projection` matrix,
+    /// labels with anchors outside the view being dropped. From the highest
+    /// priority down, each label is measured with
+    /// [`glyph_bounds`](trait.GlyphCruncher.html#method.glyph_bounds) then
+    /// queued at the first candidate position of the `placer` it fits.
+    pub fn queue_labels<'a, I>(
+        &mut self,
+        labels: I,
+        placer: &LabelPlacer,
+        view_projection: [f32; 16],
+        target_width: u32,
+        target_height: u32,
+    ) -> Vec<usize>
+    where
+        I: IntoIterator<Item = Label<'a>>,
+    {
+        let target = (target_width as f32, target_height as f32);
+        let mut labels: Vec<_> = labels
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, label)| {
+                let anchor = label::project(&view_projection, label.anchor, target)?;
+                Some((index, anchor, label))
+            })
+            .collect();
+        labels.sort_by(|(_, _, a), (_, _, b)| b.priority.total_cmp(&a.priority));
+
+        let mut placed = Occupancy::default();
+        let mut queued = Vec::new();
+        for (index, anchor, label) in labels {
+            let mut section = label.section;
+            section.screen_position = (0.0, 0.0);
+            let Some(bounds) = self.glyph_bounds(&section) else {
+                continue;
+            };
+
+            if let Some(rect) = placer.place(&placed, anchor, bounds) {
+                section.screen_position = (rect.min.x - bounds.min.x, rect.min.y - bounds.min.y);
+                placed.insert(rect);
+                queued.push(index);
+                self.queue(section);
+            }
+        }
+
+        queued.sort_unstable();
+        queued
+    }
+
+    /// Queues a section/layout to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), the glyphs
+    /// of each `Text` animated by the [`Effect`](enum.Effect.html) at the same
+    /// index, e.g. wobbling or color cycling words of a dialog.
+    ///
+    /// Effects are evaluated in the vertex shader at the time set by
+    /// [`set_effect_time`](struct.GlyphBrush.html#method.set_effect_time),
+    /// so that animating them needs no relayout nor vertex upload.
+    pub fn queue_with_effects<'a, S>(&mut self, section: S, effects: &[Effect])
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let mut section = self.extend_section(&section.into(), self.default_fade());
+        for (text, effect) in section.text.iter_mut().zip(effects) {
+            text.extra.effect = effect.id();
+            text.extra.effect_params = effect.params();
+        }
+        self.queue_extended(section)
+    }
+
+    /// Sets the time in seconds at which the next draw calls evaluate glyph
+    /// effects, see
+    /// [`queue_with_effects`](struct.GlyphBrush.html#method.queue_with_effects).
+    #[inline]
+    pub fn set_effect_time(&mut self, seconds: f32) {
+        self.pipeline.set_time(seconds);
+    }
+
+    /// Queues a section/layout to be drawn once per stamp by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), each copy
+    /// moved from the section's `screen_position` to the stamp's position,
+    /// scaled & tinted, e.g. floating damage numbers.
+    ///
+    /// The section is laid out & its glyph quads uploaded once, while the
+    /// stamps are uploaded as a separate instance stream each frame, so that
+    /// the cost of copies doesn't grow with their text. Copies are drawn over
+    /// queued sections, keeping the synthetic styling & fading of their
+    /// glyphs.
+    pub fn queue_stamped<'a, S>(&mut self, section: S, stamps: &[Stamp])
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        self.queue_stamped_with_effects(section, &[], stamps)
+    }
+
+    /// Queues a section/layout to be drawn once per stamp like
+    /// [`queue_stamped`](#method.queue_stamped), the glyphs of each `Text`
+    /// animated by the [`Effect`](enum.Effect.html) at the same index like
+    /// [`queue_with_effects`](#method.queue_with_effects). Copies are
+    /// animated in step.
+    pub fn queue_stamped_with_effects<'a, S>(
+        &mut self,
+        section: S,
+        effects: &[Effect],
+        stamps: &[Stamp],
+    ) where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let mut section = self.extend_section(&section.into(), self.default_fade());
+        self.stamps.push((stamps.to_vec(), section.screen_position));
+        let stamp = self.stamps.len() as u32;
+        for text in &mut section.text {
+            text.extra.stamp = stamp;
+        }
+        for (text, effect) in section.text.iter_mut().zip(effects) {
+            text.extra.effect = effect.id();
+            text.extra.effect_params = effect.params();
+        }
+        self.queue_extended(section)
+    }
+
+    /// Queues a section/layout to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), showing
+    /// only its glyphs revealed by a typewriter animation, e.g. dialog text.
+    /// Returns whether all glyphs are revealed.
+    ///
+    /// Glyphs keep the positions of the full text, laid out once & cached,
+    /// so that words do not move between lines while being typed. Hidden
+    /// glyphs are left out and the edge fades in through the glyph color.
+    pub fn queue_revealed<'a, S>(&mut self, section: S, reveal: Reveal) -> bool
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let section = section.into();
+        let geometry = SectionGeometry::from(&*section);
+        let bounds = match &self.shaping {
+            Some(shaping) => shaping.with_layout(section.layout).bounds_rect(&geometry),
+            None => section.layout.bounds_rect(&geometry),
+        };
+        let glyphs: Vec<_> = self.glyphs(&*section).cloned().collect();
+        let section = self.extend_section(&section, self.default_fade());
+
+        let alpha = reveal.alphas(&glyphs);
+
+        let complete = alpha.iter().all(|&a| a >= 1.0);
+        let (glyphs, extra) = per_glyph(
+            glyphs
+                .into_iter()
+                .zip(alpha)
+                .filter(|(_, alpha)| *alpha > 0.0),
+            |(sg, alpha)| {
+                let mut extra = section.text[sg.section_index].extra;
+                extra.extra.color[3] *= alpha;
+                (sg, extra)
+            },
+        );
+
+        self.glyph_brush.queue_pre_positioned(glyphs, extra, bounds);
+        complete
+    }
+
+    /// Retains a section/layout, drawn by each draw call from the next call of
+    /// [`process_queued`](struct.GlyphBrush.html#method.process_queued) until
+    /// removed, without being queued every frame.
+    ///
+    /// Retained texts are laid out once, like sections passed to
+    /// [`queue`](struct.GlyphBrush.html#method.queue), & drawn from their own
+    /// instance buffer & glyph texture. Each text keeps a slot of the buffer
+    /// written in place when it is updated, shown or hidden. They are drawn in
+    /// the current [layer](struct.GlyphBrush.html#method.set_layer), before
+    /// the sections queued in it.
+    pub fn create_text<'a, S>(&mut self, section: S) -> TextHandle
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let text = self.retained_text(&section.into());
+        self.retained.insert(text)
+    }
+
+    /// Replaces the section/layout of a retained text, in the current layer,
+    /// returning `false` if the handle was removed.
+    pub fn update_text<'a, S>(&mut self, handle: TextHandle, section: S) -> bool
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        if self.retained.get(handle).is_none() {
+            return false;
+        }
+        let text = self.retained_text(&section.into());
+        self.retained.replace(handle, text)
+    }
+
+    /// Stops drawing a retained text, returning `false` if the handle was
+    /// already removed.
+    #[inline]
+    pub fn remove_text(&mut self, handle: TextHandle) -> bool {
+        self.retained.remove(handle)
+    }
+
+    /// Shows or hides a retained text, hidden texts keeping their glyphs
+    /// cached. Returns `false` if the handle was removed.
+    #[inline]
+    pub fn set_text_visible(&mut self, handle: TextHandle, visible: bool) -> bool {
+        self.retained.set_visible(handle, visible)
+    }
+
+    /// Returns whether a retained text is visible, `None` if the handle was
+    /// removed.
+    #[inline]
+    pub fn is_text_visible(&self, handle: TextHandle) -> Option<bool> {
+        self.retained.get(handle).map(|text| text.visible)
+    }
+
+    /// Sets the layer of the sections queued & texts retained from now on,
+    /// `0` initially.
+    ///
+    /// [`draw_queued_layer`](struct.GlyphBrush.html#method.draw_queued_layer)
+    /// draws the sections of one layer, other draw calls all of them in
+    /// ascending layer order.
+    #[inline]
+    pub fn set_layer(&mut self, layer: u32) {
+        self.layer = layer;
+    }
+
+    /// Returns the layer of the sections queued from now on.
+    #[inline]
+    pub fn layer(&self) -> u32 {
+        self.layer
+    }
+
+    /// Queues a section/layout in a layer to be drawn by the next call of
+    /// [`draw_queued_layer`](struct.GlyphBrush.html#method.draw_queued_layer),
+    /// see [`set_layer`](struct.GlyphBrush.html#method.set_layer).
+    #[inline]
+    pub fn queue_in_layer<'a, S>(&mut self, section: S, layer: u32)
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let current = std::mem::replace(&mut self.layer, layer);
+        self.queue(section);
+        self.layer = current;
+    }
+
+    /// Queues a section/layout to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), rotated
+    /// & scaled about a pivot, so that differently transformed sections are
+    /// drawn together.
+    ///
+    /// The transform applies to glyph quads when drawn, see
+    /// [`Instance::local_transform`](struct.Instance.html#structfield.local_transform).
+    /// Changing it does not lay the section out again.
+    pub fn queue_transformed<'a, S>(&mut self, section: S, transform: SectionTransform)
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let mut section = self.extend_section(&section.into(), self.default_fade());
+        let (x, y) = transform.pivot.unwrap_or(section.screen_position);
+        for text in &mut section.text {
+            text.extra.transform = transform.matrix();
+            text.extra.pivot = [x, y];
+        }
+        self.queue_extended(section)
+    }
+
+    /// Queues a section/layout to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), shrinking
+    /// its text to the largest scale, up to the requested `Text` scales, at
+    /// which it fits the section bounds.
+    ///
+    /// The scale is found with a binary search over
+    /// [`glyph_bounds`](trait.GlyphCruncher.html#method.glyph_bounds) and
+    /// cached by the section's texts, scales, fonts, bounds & layout while it
+    /// is queued each frame, so moving or recoloring it doesn't search again.
+    pub fn queue_shrink_to_fit<'a, S>(&mut self, section: S)
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let section = section.into();
+        let mut fit_cache = std::mem::take(&mut self.fit_cache);
+        let factor = fit_cache.factor(fit::fit_hash(&section), || {
+            // measure unbounded, so that no overflow is cut off by the layout
+            let (bound_w, bound_h) = section.bounds;
+            let mut measured = section.clone().into_owned();
+            measured.bounds = match measured.layout {
+                Layout::SingleLine { .. } => (f32::INFINITY, f32::INFINITY),
+                Layout::Wrap { .. } => (bound_w, f32::INFINITY),
+            };
+
+            fit::search(
+                |factor| match self.glyph_bounds(scaled(&measured, factor)) {
+                    Some(rect) => {
+                        let tolerance = rect.height().max(rect.width()) * 1e-4;
+                        rect.width() <= bound_w + tolerance && rect.height() <= bound_h + tolerance
+                    }
+                    None => true,
+                },
+            )
+        });
+        self.fit_cache = fit_cache;
+
+        match factor {
+            f if f < 1.0 => self.queue(scaled(&section, f)),
+            _ => self.queue(section),
+        }
     }
 
     /// Queues a section/layout to be drawn by the next call of
@@ -110,7 +555,9 @@ where
     ///
     /// Used to provide custom `GlyphPositioner` logic, if using built-in
     /// [`Layout`](enum.Layout.html) simply use
-    /// [`queue`](struct.GlyphBrush.html#method.queue)
+    /// [`queue`](struct.GlyphBrush.html#method.queue), if using a
+    /// [`ShapedLayout`](struct.ShapedLayout.html) use
+    /// [`queue_shaped`](struct.GlyphBrush.html#method.queue_shaped)
     ///
     /// Benefits from caching, see [caching behaviour](#caching-behaviour).
     #[inline]
@@ -119,12 +566,111 @@ where
         G: GlyphPositioner,
         S: Into<Cow<'a, Section<'a>>>,
     {
-        self.glyph_brush.queue_custom_layout(section, custom_layout)
+        let section = self.extend_section(&section.into(), 0.0);
+        self.queue_laid_out(section, custom_layout)
+    }
+
+    /// Queues a section/layout to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), shaped by
+    /// a [`ShapedLayout`](struct.ShapedLayout.html) other than the default
+    /// one.
+    ///
+    /// Unlike [`queue_custom_layout`](#method.queue_custom_layout), glyphs
+    /// fade out when the layout's overflow is
+    /// [`Overflow::Fade`](enum.Overflow.html#variant.Fade).
+    pub fn queue_shaped<'a, S, L>(&mut self, section: S, layout: &ShapedLayout<L>)
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+        L: LineBreaker,
+    {
+        let section = self.extend_section(&section.into(), layout.overflow_mode().fade());
+        let layout = layout.clone().with_faces(&self.faces);
+        self.queue_laid_out(section, &layout)
+    }
+
+    /// Queues a section to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), laid out
+    /// in vertical columns.
+    ///
+    /// Unlike [`queue_custom_layout`](#method.queue_custom_layout), glyphs
+    /// of sideways runs such as Latin are rotated a quarter turn clockwise
+    /// through [`Instance::rotation`](struct.Instance.html#structfield.rotation),
+    /// see [`VerticalLayout`](struct.VerticalLayout.html). Rotated glyphs are
+    /// not clipped to the section bounds.
+    ///
+    /// Fonts are shaped with the [faces](#method.add_font_face) & at the
+    /// [variation coordinates](#method.add_font_variation) registered with
+    /// the brush, unless the layout sets its own.
+    pub fn queue_vertical<'a, S, L>(&mut self, section: S, layout: &VerticalLayout<L>)
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+        L: LineBreaker,
+    {
+        let section = self.extend_section(&section.into(), 0.0);
+        let layout = layout.clone().with_faces(&self.faces);
+
+        let bounds = layout.bounds_rect(&SectionGeometry::from(&section));
+        let glyphs: Vec<_> = self
+            .glyph_brush
+            .glyphs_custom_layout(&section, &layout)
+            .cloned()
+            .collect();
+
+        // one extra per glyph, holding its rotation
+        let (glyphs, extra) = per_glyph(glyphs, |sg| {
+            let text = &section.text[sg.section_index];
+            let extra = GlyphExtra {
+                rotation: VerticalLayout::<L>::rotation(text.text, sg.byte_index),
+                ..text.extra
+            };
+            (sg, extra)
+        });
+
+        self.glyph_brush.queue_pre_positioned(glyphs, extra, bounds)
+    }
+
+    /// Queues a section to be drawn by the next call of
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued), laid out
+    /// on a single line whose baseline follows the path, e.g. a road or the
+    /// rim of a badge.
+    ///
+    /// Each glyph is centered at its arc length along the path, rotated to
+    /// its direction there through
+    /// [`Instance::rotation`](struct.Instance.html#structfield.rotation).
+    /// The section's horizontal alignment places the text from the start,
+    /// around the middle or up to the end of the path, its position & bounds
+    /// are unused. Glyphs beyond the ends of the path are dropped.
+    pub fn queue_on_path<'a, S>(&mut self, section: S, path: &TextPath)
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let mut section = section.into().into_owned();
+        let h_align = match section.layout {
+            Layout::SingleLine { h_align, .. } | Layout::Wrap { h_align, .. } => h_align,
+        };
+        section.screen_position = (0.0, 0.0);
+        section.bounds = (f32::INFINITY, f32::INFINITY);
+        section.layout = Layout::default_single_line();
+
+        let glyphs: Vec<_> = self.glyphs(&section).cloned().collect();
+        let placed = place_on_path(self.fonts(), glyphs, path, h_align);
+
+        let section = self.extend_section(&section, self.default_fade());
+        let (glyphs, extra) = per_glyph(placed, |(sg, rotation)| {
+            let extra = GlyphExtra {
+                rotation,
+                ..section.text[sg.section_index].extra
+            };
+            (sg, extra)
+        });
+
+        self.glyph_brush
+            .queue_pre_positioned(glyphs, extra, UNBOUNDED)
     }
 
     /// Queues pre-positioned glyphs to be processed by the next call of
-    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued). Can be
-    /// called multiple times.
+    /// [`draw_queued`](struct.GlyphBrush.html#method.draw_queued) in the
+    /// current [layer](#method.set_layer). Can be called multiple times.
     #[inline]
     pub fn queue_pre_positioned(
         &mut self,
@@ -132,6 +678,14 @@ where
         extra: Vec<Extra>,
         bounds: Rect,
     ) {
+        let layer = self.layer;
+        let extra = extra
+            .into_iter()
+            .map(|extra| GlyphExtra {
+                layer,
+                ..GlyphExtra::from(extra)
+            })
+            .collect();
         self.glyph_brush.queue_pre_positioned(glyphs, extra, bounds)
     }
 
@@ -146,6 +700,7 @@ where
         S: Into<Cow<'a, Section<'a>>>,
         G: GlyphPositioner,
     {
+        let section = self.extend_section(&section.into(), 0.0);
         self.glyph_brush
             .keep_cached_custom_layout(section, custom_layout)
     }
@@ -160,7 +715,13 @@ where
     where
         S: Into<Cow<'a, Section<'a>>>,
     {
-        self.glyph_brush.keep_cached(section)
+        let section = self.extend_section(&section.into(), self.default_fade());
+        if let Some(shaping) = &self.shaping {
+            let layout = shaping.with_layout(section.layout);
+            self.glyph_brush.keep_cached_custom_layout(section, &layout)
+        } else {
+            self.glyph_brush.keep_cached(section)
+        }
     }
 
     /// Returns the available fonts.
@@ -177,6 +738,156 @@ where
     pub fn add_font(&mut self, font: F) -> FontId {
         self.glyph_brush.add_font(font)
     }
+
+    /// Registers the face of the font `font_id`, its data parsed for text
+    /// shaping, on the default [`ShapedLayout`](struct.ShapedLayout.html) &
+    /// the layouts queued through the brush.
+    ///
+    /// Fonts without a face are laid out unshaped, see
+    /// [`add_font_face`](#method.add_font_face) to add a font with its face.
+    pub fn set_font_face(&mut self, font_id: FontId, face: FontFace) {
+        self.shaping = self
+            .shaping
+            .take()
+            .map(|shaping| shaping.font_face(font_id, face.clone()));
+        self.faces.insert(font_id, face);
+    }
+
+    /// Adds a font as the face of a family for a weight & style, see
+    /// [`FontFamilies`](struct.FontFamilies.html).
+    ///
+    /// Returns a new [`FontId`](struct.FontId.html) to reference this font.
+    pub fn add_font_variant(
+        &mut self,
+        family: &str,
+        weight: FontWeight,
+        style: FontStyle,
+        font: F,
+    ) -> FontId {
+        let font_id = self.glyph_brush.add_font(font);
+        self.families.insert(family, weight, style, font_id);
+        font_id
+    }
+
+    /// Returns the registered font families.
+    #[inline]
+    pub fn font_families(&self) -> &FontFamilies {
+        &self.families
+    }
+
+    /// Resolves the [`FontId`](struct.FontId.html) of the family's face
+    /// closest to the weight & style, to use with
+    /// [`Text::with_font_id`](struct.Text.html#method.with_font_id).
+    ///
+    /// Falls back to the nearest available face when the variant is missing,
+    /// see [`FontFamilies::resolve`](struct.FontFamilies.html#method.resolve).
+    pub fn font_id(&self, family: &str, weight: FontWeight, style: FontStyle) -> Option<FontId> {
+        self.families
+            .resolve(family, weight, style)
+            .map(|font_match| font_match.font_id)
+    }
+}
+
+impl<B, F: Font + From<FontArc>, H: BuildHasher> GlyphBrush<B, F, H>
+where
+    B: GlyphBrushBackend,
+{
+    /// Adds the font of a face, registering the face for shaping, see
+    /// [`set_font_face`](#method.set_font_face).
+    ///
+    /// Returns a new [`FontId`](struct.FontId.html) to reference this font.
+    pub fn add_font_face(&mut self, face: FontFace) -> FontId {
+        let font_id = self.glyph_brush.add_font(face.font());
+        self.set_font_face(font_id, face);
+        font_id
+    }
+
+    /// Adds an instance of the variable font `font_id` at the axis
+    /// coordinates, e.g. `[Variation::weight(650.0), Variation::width(85.0)]`.
+    /// Axes left out, or missing from the font, keep their default value.
+    ///
+    /// Returns a [`FontId`](struct.FontId.html) to reference this instance,
+    /// the same one for repeated requests. Glyphs are cached per `FontId`, so
+    /// instances are rasterized separately while sharing the glyph texture.
+    /// Instances of a font share a single copy of its data.
+    ///
+    /// The font must have a [registered face](#method.set_font_face), which
+    /// the instance is made from, otherwise `InvalidFont` is returned.
+    pub fn add_font_variation(
+        &mut self,
+        font_id: FontId,
+        variations: &[Variation],
+    ) -> Result<FontId, InvalidFont> {
+        let key = (font_id, variations.to_vec());
+        if let Some(variation_id) = self.variations.get(&key) {
+            return Ok(*variation_id);
+        }
+
+        let face = self.faces.get(font_id).cloned().ok_or(InvalidFont)?;
+        let font = face.instance(variations);
+
+        let variation_id = self.glyph_brush.add_font(FontArc::new(font));
+        self.set_font_face(variation_id, face);
+        self.shaping = self
+            .shaping
+            .take()
+            .map(|shaping| shaping.font_variations(variation_id, key.1.clone()));
+        self.faces.insert_variations(variation_id, key.1.clone());
+        let _ = self.variations.insert(key, variation_id);
+
+        Ok(variation_id)
+    }
+}
+
+impl<B, F, H: BuildHasher> GlyphBrush<B, F, H>
+where
+    B: GlyphBrushBackend,
+    F: Font + Clone + Send + Sync + From<FontArc> + 'static,
+{
+    /// Adds a synthetic bold and/or oblique style of the font `font_id`, for
+    /// fonts lacking such faces.
+    ///
+    /// Emboldening rasterizes outlines twice, horizontally offset, growing
+    /// advances to match. Obliques are sheared about the baseline of each
+    /// glyph when drawn, so sections using them are queued glyph by glyph,
+    /// their vertices not cached between frames.
+    ///
+    /// Returns a [`FontId`](struct.FontId.html) to reference this style, the
+    /// same one for repeated requests.
+    pub fn add_synthetic_font(&mut self, font_id: FontId, synthesis: Synthesis) -> FontId {
+        if synthesis.is_none() {
+            return font_id;
+        }
+        if let Some(synthetic_id) = self.synthetic.get(&(font_id, synthesis)) {
+            return *synthetic_id;
+        }
+
+        let font = self.glyph_brush.fonts()[font_id.0].clone();
+        let synthetic_id = self
+            .glyph_brush
+            .add_font(FontArc::new(SyntheticFont::new(font, synthesis)));
+
+        let _ = self.synthetic.insert((font_id, synthesis), synthetic_id);
+        if synthesis.skew != 0.0 {
+            let _ = self.skews.insert(synthetic_id, synthesis.skew);
+        }
+
+        synthetic_id
+    }
+
+    /// Resolves the [`FontId`](struct.FontId.html) of the family's face
+    /// closest to the weight & style like
+    /// [`font_id`](struct.GlyphBrush.html#method.font_id), synthesizing the
+    /// bold or oblique styling the family lacks.
+    pub fn resolve_font(
+        &mut self,
+        family: &str,
+        weight: FontWeight,
+        style: FontStyle,
+    ) -> Option<FontId> {
+        let font_match = self.families.resolve(family, weight, style)?;
+        Some(self.add_synthetic_font(font_match.font_id, font_match.synthesis()))
+    }
 }
 
 impl<B, F: Font + Sync, H: BuildHasher> GlyphBrush<B, F, H>
@@ -223,7 +934,119 @@ where
         transform: [f32; 16],
     ) -> Result<(), PipelineError> {
         //self.process_queued(context);
-        self.pipeline.draw(pipeline, shading_gate, transform, None)
+        self.pipeline
+            .draw(pipeline, shading_gate, transform, None, None, None)
+    }
+
+    /// Draws all queued sections onto a render target, those queued with
+    /// [`queue_world`](struct.GlyphBrush.html#method.queue_world) through the
+    /// camera's column-major view & projection matrices, others in pixels.
+    /// See [`queue`](struct.GlyphBrush.html#method.queue).
+    ///
+    /// Trims the cache, see [caching behaviour](#caching-behaviour).
+    ///
+    /// # Panics
+    /// Panics if the provided `target` has a texture format that does not match
+    /// the `render_format` provided on creation of the `GlyphBrush`.
+    #[inline]
+    pub fn draw_queued_3d<'a>(
+        &mut self,
+        pipeline: &mut LuminancePipeline<'a, B>,
+        shading_gate: &mut ShadingGate<'a, B>,
+        target_width: u32,
+        target_height: u32,
+        view: [f32; 16],
+        projection: [f32; 16],
+    ) -> Result<(), PipelineError> {
+        self.pipeline.draw(
+            pipeline,
+            shading_gate,
+            orthographic_projection(target_width, target_height),
+            Some((view, projection)),
+            None,
+            None,
+        )
+    }
+
+    /// Draws the sections queued & texts retained in a layer onto a render
+    /// target, see [`set_layer`](struct.GlyphBrush.html#method.set_layer),
+    /// e.g. text under & over sprites drawn in between. Draws nothing if no
+    /// section was queued or text retained in the layer.
+    ///
+    /// Each layer has instance buffers of its own, uploaded by
+    /// [`process_queued`](struct.GlyphBrush.html#method.process_queued) &
+    /// drawn in order: retained texts first, then queued sections, then
+    /// stamped copies.
+    ///
+    /// # Panics
+    /// Panics if the provided `target` has a texture format that does not match
+    /// the `render_format` provided on creation of the `GlyphBrush`.
+    #[inline]
+    pub fn draw_queued_layer<'a>(
+        &mut self,
+        layer: u32,
+        pipeline: &mut LuminancePipeline<'a, B>,
+        shading_gate: &mut ShadingGate<'a, B>,
+        target_width: u32,
+        target_height: u32,
+    ) -> Result<(), PipelineError> {
+        self.draw_queued_layer_with_transform(
+            layer,
+            pipeline,
+            shading_gate,
+            orthographic_projection(target_width, target_height),
+        )
+    }
+
+    /// Draws the sections queued & texts retained in a layer onto a render
+    /// target, applying a position transform (e.g. a projection), see
+    /// [`draw_queued_layer`](struct.GlyphBrush.html#method.draw_queued_layer).
+    ///
+    /// # Panics
+    /// Panics if the provided `target` has a texture format that does not match
+    /// the `render_format` provided on creation of the `GlyphBrush`.
+    #[inline]
+    pub fn draw_queued_layer_with_transform<'a>(
+        &mut self,
+        layer: u32,
+        pipeline: &mut LuminancePipeline<'a, B>,
+        shading_gate: &mut ShadingGate<'a, B>,
+        transform: [f32; 16],
+    ) -> Result<(), PipelineError> {
+        self.pipeline
+            .draw(pipeline, shading_gate, transform, None, Some(layer), None)
+    }
+
+    /// Draws the sections queued & texts retained in a layer onto a render
+    /// target, those queued with
+    /// [`queue_world`](struct.GlyphBrush.html#method.queue_world) through the
+    /// camera's view & projection, see
+    /// [`draw_queued_3d`](struct.GlyphBrush.html#method.draw_queued_3d) &
+    /// [`draw_queued_layer`](struct.GlyphBrush.html#method.draw_queued_layer).
+    ///
+    /// # Panics
+    /// Panics if the provided `target` has a texture format that does not match
+    /// the `render_format` provided on creation of the `GlyphBrush`.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_queued_layer_3d<'a>(
+        &mut self,
+        layer: u32,
+        pipeline: &mut LuminancePipeline<'a, B>,
+        shading_gate: &mut ShadingGate<'a, B>,
+        target_width: u32,
+        target_height: u32,
+        view: [f32; 16],
+        projection: [f32; 16],
+    ) -> Result<(), PipelineError> {
+        self.pipeline.draw(
+            pipeline,
+            shading_gate,
+            orthographic_projection(target_width, target_height),
+            Some((view, projection)),
+            Some(layer),
+            None,
+        )
     }
 
     /// Draws all queued sections onto a render target, applying a position
@@ -245,7 +1068,7 @@ where
     ) -> Result<(), PipelineError> {
         //self.process_queued(context);
         self.pipeline
-            .draw(pipeline, shading_gate, transform, Some(region))
+            .draw(pipeline, shading_gate, transform, None, None, Some(region))
     }
 
     pub fn process_queued<C>(&mut self, context: &mut C)
@@ -262,6 +1085,10 @@ where
     ) where
         C: GraphicsContext<Backend = B>,
     {
+        self.fit_cache.trim();
+        self.update_retained(context, &into_vertex);
+        self.pipeline
+            .update_transforms(context, &self.transforms.take());
         let pipeline = &mut self.pipeline;
 
         let mut brush_action;
@@ -274,21 +1101,18 @@ where
 
                     pipeline.update_cache(offset, size, tex_data);
                 },
-                &into_vertex,
+                |vertex| glyph_instance(&into_vertex, vertex),
             );
 
             match brush_action {
                 Ok(_) => break,
                 Err(BrushError::TextureTooSmall { suggested }) => {
-                    // TODO: Obtain max texture dimensions
-                    let max_image_dimension = 2048;
-
-                    let (new_width, new_height) = if (suggested.0 > max_image_dimension
-                        || suggested.1 > max_image_dimension)
-                        && (self.glyph_brush.texture_dimensions().0 < max_image_dimension
-                            || self.glyph_brush.texture_dimensions().1 < max_image_dimension)
+                    let (new_width, new_height) = if (suggested.0 > MAX_IMAGE_DIMENSION
+                        || suggested.1 > MAX_IMAGE_DIMENSION)
+                        && (self.glyph_brush.texture_dimensions().0 < MAX_IMAGE_DIMENSION
+                            || self.glyph_brush.texture_dimensions().1 < MAX_IMAGE_DIMENSION)
                     {
-                        (max_image_dimension, max_image_dimension)
+                        (MAX_IMAGE_DIMENSION, MAX_IMAGE_DIMENSION)
                     } else {
                         suggested
                     };
@@ -311,10 +1135,84 @@ where
 
         match brush_action.unwrap() {
             BrushAction::Draw(verts) => {
-                self.pipeline.upload(context, &verts);
+                self.pipeline.upload(context, verts);
             }
             BrushAction::ReDraw => {}
         };
+
+        self.pipeline
+            .upload_stamps(context, &std::mem::take(&mut self.stamps));
+    }
+
+    /// Uploads the instances of the retained texts changed since the last
+    /// call, caching their glyphs in the retained texture. All texts are
+    /// uploaded again if cached glyphs moved.
+    fn update_retained<C>(
+        &mut self,
+        context: &mut C,
+        into_vertex: &impl Fn(GlyphVertex) -> Instance,
+    ) where
+        C: GraphicsContext<Backend = B>,
+    {
+        let changed = self.retained.take_changed();
+        if changed.is_empty() {
+            return;
+        }
+
+        let retained = self.pipeline.retained();
+        let glyphs = self
+            .retained
+            .iter()
+            .flat_map(|(_, text)| text.glyphs.iter());
+        let moved = retained.cache_glyphs(
+            context,
+            self.glyph_brush.fonts(),
+            glyphs,
+            MAX_IMAGE_DIMENSION,
+        );
+
+        // removed texts are only among the changed ones
+        let mut handles = changed;
+        if moved {
+            handles.extend(self.retained.iter().map(|(handle, _)| handle));
+        }
+        for handle in handles {
+            let text = match self.retained.get(handle) {
+                Some(text) => text,
+                None => {
+                    retained.remove(handle);
+                    continue;
+                }
+            };
+
+            let mut instances = Vec::new();
+            for sg in text.glyphs.iter().filter(|_| text.visible) {
+                let (tex_coords, pixel_coords) = match retained.rect_for(sg.font_id.0, &sg.glyph) {
+                    Some(rects) => rects,
+                    None => continue,
+                };
+                // skip glyphs wholly out of bounds, like queued ones
+                let bounds = text.bounds;
+                if pixel_coords.min.x > bounds.max.x
+                    || pixel_coords.min.y > bounds.max.y
+                    || bounds.min.x > pixel_coords.max.x
+                    || bounds.min.y > pixel_coords.max.y
+                {
+                    continue;
+                }
+
+                let vertex = glyph_brush::GlyphVertex {
+                    tex_coords,
+                    pixel_coords,
+                    bounds,
+                    extra: &text.extra[sg.section_index],
+                };
+                instances.push(glyph_instance(into_vertex, vertex).instance);
+            }
+            retained.set(handle, text.layer, instances);
+        }
+
+        retained.upload(context);
     }
 }
 
@@ -322,20 +1220,224 @@ impl<B, F: Font, H: BuildHasher> GlyphBrush<B, F, H>
 where
     B: GlyphBrushBackend,
 {
-    fn new<C>(context: &mut C, raw_builder: glyph_brush::GlyphBrushBuilder<F, H>) -> Self
+    /// Lays a section out to be retained, see `create_text`.
+    fn retained_text(&mut self, section: &Section<'_>) -> RetainedText {
+        let section = self.extend_section(section, self.default_fade());
+        let (glyphs, bounds) = match &self.shaping {
+            Some(shaping) => {
+                let layout = shaping.with_layout(section.layout);
+                let glyphs: Vec<_> = self
+                    .glyph_brush
+                    .glyphs_custom_layout(&section, &layout)
+                    .cloned()
+                    .collect();
+                (glyphs, layout.bounds_rect(&SectionGeometry::from(&section)))
+            }
+            None => {
+                let layout = section.layout;
+                let glyphs: Vec<_> = self
+                    .glyph_brush
+                    .glyphs_custom_layout(&section, &layout)
+                    .cloned()
+                    .collect();
+                (glyphs, layout.bounds_rect(&SectionGeometry::from(&section)))
+            }
+        };
+        let (glyphs, extra) = per_glyph(glyphs, |sg| {
+            let extra = section.text[sg.section_index].extra;
+            (sg, extra)
+        });
+
+        RetainedText {
+            glyphs,
+            extra,
+            bounds,
+            layer: self.layer,
+            visible: true,
+        }
+    }
+
+    /// Returns the fade out width of the default layout, see `Overflow::Fade`.
+    fn default_fade(&self) -> f32 {
+        self.shaping
+            .as_ref()
+            .map_or(0.0, |shaping| shaping.overflow_mode().fade())
+    }
+
+    fn queue_extended(&mut self, section: Section<'_, GlyphExtra>) {
+        if let Some(shaping) = &self.shaping {
+            let layout = shaping.with_layout(section.layout);
+            self.queue_laid_out(section, &layout)
+        } else {
+            let layout = section.layout;
+            self.queue_laid_out(section, &layout)
+        }
+    }
+
+    /// Queues a section with resolved extras laid out by `layout`, glyph by
+    /// glyph if synthetic obliques need their baselines.
+    fn queue_laid_out<L>(&mut self, section: Section<'_, GlyphExtra>, layout: &L)
+    where
+        L: GlyphPositioner,
+    {
+        if section.text.iter().all(|text| text.extra.skew == 0.0) {
+            return self.glyph_brush.queue_custom_layout(section, layout);
+        }
+
+        let bounds = layout.bounds_rect(&SectionGeometry::from(&section));
+        let glyphs: Vec<_> = self
+            .glyph_brush
+            .glyphs_custom_layout(&section, layout)
+            .cloned()
+            .collect();
+        let (glyphs, extra) = per_glyph(glyphs, |sg| {
+            let extra = section.text[sg.section_index].extra;
+            (sg, extra)
+        });
+        self.glyph_brush.queue_pre_positioned(glyphs, extra, bounds)
+    }
+
+    /// Resolves vertex generation data of each `Text` from its `FontId`,
+    /// fading out over `fade` pixels before the right bound, see
+    /// `Overflow::Fade`.
+    fn extend_section<'a>(&self, section: &Section<'a>, fade: f32) -> Section<'a, GlyphExtra> {
+        extend_section(section, |text| GlyphExtra {
+            extra: text.extra,
+            skew: self.skews.get(&text.font_id).copied().unwrap_or(0.0),
+            fade,
+            layer: self.layer,
+            ..GlyphExtra::from(text.extra)
+        })
+    }
+
+    fn new<C>(
+        context: &mut C,
+        raw_builder: glyph_brush::GlyphBrushBuilder<F, H>,
+        shaping: Option<ShapedLayout>,
+        families: FontFamilies,
+        faces: Faces,
+    ) -> Self
     where
         C: GraphicsContext<Backend = B>,
     {
+        let retained_cache = raw_builder.draw_cache_builder.clone().dimensions(256, 256);
         let glyph_brush = raw_builder.build();
         let (cache_width, cache_height) = glyph_brush.texture_dimensions();
 
         GlyphBrush {
-            pipeline: Pipeline::new(context, cache_width, cache_height),
+            pipeline: Pipeline::new(context, cache_width, cache_height, retained_cache),
             glyph_brush,
+            shaping,
+            families,
+            variations: HashMap::new(),
+            faces,
+            synthetic: HashMap::new(),
+            skews: HashMap::new(),
+            fit_cache: FitCache::default(),
+            transforms: TransformQueue::default(),
+            layer: 0,
+            retained: RetainedTexts::default(),
+            stamps: Vec::new(),
         }
     }
 }
 
+const UNBOUNDED: Rect = Rect {
+    min: ab_glyph::Point {
+        x: f32::NEG_INFINITY,
+        y: f32::NEG_INFINITY,
+    },
+    max: ab_glyph::Point {
+        x: f32::INFINITY,
+        y: f32::INFINITY,
+    },
+};
+
+/// Largest glyph texture dimension.
+// TODO: Obtain max texture dimensions
+const MAX_IMAGE_DIMENSION: u32 = 2048;
+
+/// Returns the instance of a glyph vertex, made by `into_vertex` & extended
+/// with its resolved extra.
+fn glyph_instance(
+    into_vertex: &impl Fn(GlyphVertex) -> Instance,
+    vertex: glyph_brush::GlyphVertex<'_, GlyphExtra>,
+) -> TaggedInstance {
+    let extra = vertex.extra;
+    let instance = into_vertex(GlyphVertex {
+        tex_coords: vertex.tex_coords,
+        pixel_coords: vertex.pixel_coords,
+        // quads are clipped before rotation, so rotated ones are not
+        bounds: match extra.rotation {
+            0.0 => vertex.bounds,
+            _ => UNBOUNDED,
+        },
+        extra: &extra.extra,
+    });
+    let instance = Instance {
+        skew: extra.skew.into(),
+        baseline: extra.baseline.into(),
+        fade: match extra.fade {
+            fade if fade > 0.0 && vertex.bounds.max.x.is_finite() => {
+                [vertex.bounds.max.x - fade, vertex.bounds.max.x].into()
+            }
+            _ => instance.fade,
+        },
+        rotation: extra.rotation.into(),
+        local_transform: extra.transform.into(),
+        pivot: extra.pivot.into(),
+        transform_index: extra.transform_index.into(),
+        billboard: extra.billboard.into(),
+        effect: extra.effect.into(),
+        effect_params: extra.effect_params.into(),
+        ..instance
+    };
+    TaggedInstance {
+        layer: extra.layer,
+        stamp: extra.stamp,
+        instance,
+    }
+}
+
+/// Returns pre-positioned glyphs with an extra each, mapped from `items`,
+/// holding the baseline of the glyph.
+fn per_glyph<T, E>(
+    items: impl IntoIterator<Item = T>,
+    mut extra: E,
+) -> (Vec<SectionGlyph>, Vec<GlyphExtra>)
+where
+    E: FnMut(T) -> (SectionGlyph, GlyphExtra),
+{
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(section_index, item)| {
+            let (sg, extra) = extra(item);
+            let extra = GlyphExtra {
+                baseline: sg.glyph.position.y,
+                ..extra
+            };
+            (
+                SectionGlyph {
+                    section_index,
+                    ..sg
+                },
+                extra,
+            )
+        })
+        .unzip()
+}
+
+/// Returns the section with the scale of each `Text` multiplied by `factor`.
+fn scaled<'a>(section: &Section<'a>, factor: f32) -> Section<'a> {
+    let mut section = section.clone();
+    for text in &mut section.text {
+        text.scale.x *= factor;
+        text.scale.y *= factor;
+    }
+    section
+}
+
 /// Helper function to generate a generate a transform matrix.
 #[rustfmt::skip]
 pub fn orthographic_projection(width: u32, height: u32) -> [f32; 16] {
@@ -361,10 +1463,26 @@ where
         L: GlyphPositioner + std::hash::Hash,
         S: Into<Cow<'a, Section<'a>>>,
     {
+        let section = self.extend_section(&section.into(), 0.0);
         self.glyph_brush
             .glyphs_custom_layout(section, custom_layout)
     }
 
+    #[inline]
+    fn glyphs<'a, 'b, S>(&'b mut self, section: S) -> SectionGlyphIter<'b>
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let section = self.extend_section(&section.into(), self.default_fade());
+        if let Some(shaping) = &self.shaping {
+            let layout = shaping.with_layout(section.layout);
+            self.glyph_brush.glyphs_custom_layout(section, &layout)
+        } else {
+            let layout = section.layout;
+            self.glyph_brush.glyphs_custom_layout(section, &layout)
+        }
+    }
+
     #[inline]
     fn glyph_bounds_custom_layout<'a, S, L>(
         &mut self,
@@ -375,10 +1493,28 @@ where
         L: GlyphPositioner + std::hash::Hash,
         S: Into<Cow<'a, Section<'a>>>,
     {
+        let section = self.extend_section(&section.into(), 0.0);
         self.glyph_brush
             .glyph_bounds_custom_layout(section, custom_layout)
     }
 
+    #[inline]
+    fn glyph_bounds<'a, S>(&mut self, section: S) -> Option<Rect>
+    where
+        S: Into<Cow<'a, Section<'a>>>,
+    {
+        let section = self.extend_section(&section.into(), self.default_fade());
+        if let Some(shaping) = &self.shaping {
+            let layout = shaping.with_layout(section.layout);
+            self.glyph_brush
+                .glyph_bounds_custom_layout(section, &layout)
+        } else {
+            let layout = section.layout;
+            self.glyph_brush
+                .glyph_bounds_custom_layout(section, &layout)
+        }
+    }
+
     #[inline]
     fn fonts(&self) -> &[F] {
         self.glyph_brush.fonts()
@@ -0,0 +1,5 @@
+mod hyphenation;
+mod kinsoku;
+
+pub use hyphenation::{HyphenationLineBreaker, Language};
+pub use kinsoku::KinsokuLineBreaker;
@@ -0,0 +1,321 @@
+use glyph_brush::{BuiltInLineBreaker, LineBreak, LineBreaker};
+use std::{collections::HashMap, sync::OnceLock};
+
+/// Language of the hyphenation patterns used by a
+/// [`HyphenationLineBreaker`](struct.HyphenationLineBreaker.html).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Language {
+    EnglishUS,
+    /// German of the 1996 reformed orthography.
+    German,
+    French,
+}
+
+impl Language {
+    fn patterns(self) -> &'static Patterns {
+        static ENGLISH_US: OnceLock<Patterns> = OnceLock::new();
+        static GERMAN: OnceLock<Patterns> = OnceLock::new();
+        static FRENCH: OnceLock<Patterns> = OnceLock::new();
+
+        match self {
+            Language::EnglishUS => ENGLISH_US.get_or_init(|| {
+                Patterns::parse(
+                    include_str!("patterns/hyph-en-us.pat.txt"),
+                    include_str!("patterns/hyph-en-us.hyp.txt"),
+                    (2, 3),
+                )
+            }),
+            Language::German => GERMAN.get_or_init(|| {
+                Patterns::parse(include_str!("patterns/hyph-de-1996.pat.txt"), "", (2, 2))
+            }),
+            Language::French => FRENCH.get_or_init(|| {
+                Patterns::parse(include_str!("patterns/hyph-fr.pat.txt"), "", (2, 3))
+            }),
+        }
+    }
+}
+
+/// A [`LineBreaker`](trait.LineBreaker.html) adding hyphenation points found
+/// with Liang's algorithm to the word boundaries of
+/// [`BuiltInLineBreaker`](enum.BuiltInLineBreaker.html).
+///
+/// Patterns of each [`Language`](enum.Language.html) are bundled & parsed on
+/// first use.
+///
+/// Lines broken within a word end with a visible hyphen when laid out by a
+/// [`ShapedLayout`](struct.ShapedLayout.html), as they do at soft hyphens
+/// (`U+00AD`).
+///
+/// ```
+/// # use luminance_glyph::{HyphenationLineBreaker, Language, ShapedLayout};
+/// let layout = ShapedLayout::default_wrap()
+///     .line_breaker(HyphenationLineBreaker::new(Language::EnglishUS));
+/// ```
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct HyphenationLineBreaker {
+    language: Language,
+}
+
+impl HyphenationLineBreaker {
+    #[inline]
+    pub fn new(language: Language) -> Self {
+        HyphenationLineBreaker { language }
+    }
+
+    /// Returns the byte indices within `word` before which it may be
+    /// hyphenated.
+    pub fn hyphenation_points(&self, word: &str) -> Vec<usize> {
+        let points = self.language.patterns().hyphenate(word);
+        word.char_indices()
+            .enumerate()
+            .filter(|(index, _)| points.contains(index))
+            .map(|(_, (byte_index, _))| byte_index)
+            .collect()
+    }
+}
+
+impl LineBreaker for HyphenationLineBreaker {
+    fn line_breaks<'a>(&self, text: &'a str) -> Box<dyn Iterator<Item = LineBreak> + 'a> {
+        let mut breaks: Vec<_> = BuiltInLineBreaker::default().line_breaks(text).collect();
+
+        for (start, word) in words(text) {
+            breaks.extend(
+                self.hyphenation_points(word)
+                    .into_iter()
+                    .map(|index| LineBreak::Soft(start + index)),
+            );
+        }
+
+        // hard breaks take precedence at the same offset
+        breaks.sort_by_key(|lb| (lb.offset(), matches!(lb, LineBreak::Soft(_))));
+        breaks.dedup_by_key(|lb| lb.offset());
+
+        Box::new(breaks.into_iter())
+    }
+}
+
+/// Returns the words of the text with their byte offset, runs of letters
+/// possibly joined by apostrophes.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let is_apostrophe = |c| c == '\'' || c == '’';
+    let mut chars = text.char_indices().peekable();
+
+    std::iter::from_fn(move || {
+        let (start, _) = chars.find(|(_, c)| c.is_alphabetic())?;
+        let mut end = text.len();
+
+        while let Some(&(index, c)) = chars.peek() {
+            let joined = is_apostrophe(c)
+                && text[index + c.len_utf8()..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphabetic);
+            if !c.is_alphabetic() && !joined {
+                end = index;
+                break;
+            }
+            let _ = chars.next();
+        }
+
+        Some((start, &text[start..end]))
+    })
+}
+
+/// Liang's hyphenation patterns of a language.
+struct Patterns {
+    /// Inter-letter values by pattern letters, `.` marking word boundaries.
+    patterns: HashMap<String, Vec<u8>>,
+    /// Length in characters of the longest pattern.
+    max_len: usize,
+    /// Hyphenation points by word, overriding patterns.
+    exceptions: HashMap<String, Vec<usize>>,
+    /// Minimum characters before the first & after the last hyphenation point.
+    minima: (usize, usize),
+}
+
+impl Patterns {
+    fn parse(patterns: &str, exceptions: &str, minima: (usize, usize)) -> Self {
+        let mut max_len = 0;
+        let patterns = patterns
+            .split_whitespace()
+            .map(|pattern| {
+                let mut letters = String::new();
+                let mut values = vec![0];
+                for c in pattern.chars() {
+                    match c.to_digit(10) {
+                        Some(value) => *values.last_mut().unwrap() = value as u8,
+                        None => {
+                            letters.push(c);
+                            values.push(0);
+                        }
+                    }
+                }
+                max_len = max_len.max(values.len() - 1);
+                (letters, values)
+            })
+            .collect();
+
+        let exceptions = exceptions
+            .split_whitespace()
+            .map(|exception| {
+                let mut word = String::new();
+                let mut points = Vec::new();
+                for c in exception.chars() {
+                    match c {
+                        '-' => points.push(word.chars().count()),
+                        _ => word.push(lowercase(c)),
+                    }
+                }
+                (word, points)
+            })
+            .collect();
+
+        Patterns {
+            patterns,
+            max_len,
+            exceptions,
+            minima,
+        }
+    }
+
+    /// Returns the character indices within `word` before which it may be
+    /// hyphenated.
+    fn hyphenate(&self, word: &str) -> Vec<usize> {
+        let word: String = word.chars().map(lowercase).collect();
+        let (left_min, right_min) = self.minima;
+        let len = word.chars().count();
+        if len < left_min + right_min {
+            return Vec::new();
+        }
+
+        if let Some(points) = self.exceptions.get(&word) {
+            return points.clone();
+        }
+
+        let chars: Vec<char> = ".".chars().chain(word.chars()).chain(".".chars()).collect();
+        let mut values = vec![0; chars.len() + 1];
+        let mut key = String::new();
+
+        for start in 0..chars.len() {
+            key.clear();
+            for &c in chars[start..].iter().take(self.max_len) {
+                key.push(c);
+                if let Some(pattern) = self.patterns.get(&key) {
+                    for (value, pattern_value) in values[start..].iter_mut().zip(pattern) {
+                        *value = (*value).max(*pattern_value);
+                    }
+                }
+            }
+        }
+
+        // values[i + 1] lies between word characters i - 1 & i
+        (left_min..=len - right_min)
+            .filter(|index| values[index + 1] % 2 == 1)
+            .collect()
+    }
+}
+
+/// Lowercases a character, keeping those without a single character
+/// lowercase form such as `'İ'`, so that character indices stay those of the
+/// word.
+fn lowercase(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the word with a hyphen at each hyphenation point.
+    fn hyphenated(language: Language, word: &str) -> String {
+        let points = HyphenationLineBreaker::new(language).hyphenation_points(word);
+        let mut hyphenated = String::new();
+        for (index, c) in word.char_indices() {
+            if points.contains(&index) {
+                hyphenated.push('-');
+            }
+            hyphenated.push(c);
+        }
+        hyphenated
+    }
+
+    #[test]
+    fn hyphenates_english() {
+        assert_eq!(
+            hyphenated(Language::EnglishUS, "hyphenation"),
+            "hy-phen-a-tion"
+        );
+        assert_eq!(hyphenated(Language::EnglishUS, "Algorithm"), "Al-go-rithm");
+        assert_eq!(hyphenated(Language::EnglishUS, "associate"), "as-so-ci-ate");
+        // at least 3 characters after the last point
+        assert_eq!(hyphenated(Language::EnglishUS, "computer"), "com-puter");
+        assert_eq!(hyphenated(Language::EnglishUS, "table"), "table");
+        assert_eq!(hyphenated(Language::EnglishUS, "an"), "an");
+    }
+
+    #[test]
+    fn hyphenates_german_and_french() {
+        assert_eq!(
+            hyphenated(Language::German, "Donaudampfschifffahrt"),
+            "Do-nau-dampf-schiff-fahrt"
+        );
+        assert_eq!(hyphenated(Language::German, "Käse"), "Kä-se");
+        assert_eq!(
+            hyphenated(Language::French, "hyphénation"),
+            "hy-phé-na-tion"
+        );
+        assert_eq!(hyphenated(Language::French, "été"), "été");
+    }
+
+    #[test]
+    fn matches_exceptions_ignoring_case() {
+        assert_eq!(hyphenated(Language::EnglishUS, "Arkansas"), "Ar-kan-sas");
+        assert_eq!(hyphenated(Language::EnglishUS, "ARKANSAS"), "AR-KAN-SAS");
+        assert_eq!(hyphenated(Language::EnglishUS, "ATPases"), "ATP-ases");
+    }
+
+    #[test]
+    fn keeps_character_indices_when_lowercasing() {
+        // 'İ' lowercases to two characters
+        assert_eq!(
+            hyphenated(Language::EnglishUS, "İhyphenation"),
+            "İhy-phen-ation"
+        );
+    }
+
+    #[test]
+    fn points_are_byte_indices() {
+        let breaker = HyphenationLineBreaker::new(Language::French);
+        assert_eq!(breaker.hyphenation_points("hyphénation"), [2, 6, 8]);
+    }
+
+    #[test]
+    fn adds_points_to_word_boundaries() {
+        let breaks: Vec<_> = HyphenationLineBreaker::new(Language::EnglishUS)
+            .line_breaks("a computer\nprogram")
+            .collect();
+        assert_eq!(
+            breaks,
+            [
+                LineBreak::Soft(2),
+                LineBreak::Soft(5),
+                LineBreak::Hard(11),
+                LineBreak::Soft(14),
+                LineBreak::Soft(18),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_words_at_non_letters() {
+        let words: Vec<_> = words("don't stop, rock’n’roll 42x").collect();
+        assert_eq!(
+            words,
+            [(0, "don't"), (6, "stop"), (12, "rock’n’roll"), (30, "x")]
+        );
+    }
+}
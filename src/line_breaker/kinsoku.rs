@@ -0,0 +1,172 @@
+use glyph_brush::{BuiltInLineBreaker, LineBreak, LineBreaker};
+
+/// A [`LineBreaker`](trait.LineBreaker.html) applying the Japanese line
+/// breaking rules (kinsoku shori) to the line breaks of
+/// [`BuiltInLineBreaker`](enum.BuiltInLineBreaker.html).
+///
+/// Lines never start with closing brackets, small kana, the prolonged sound
+/// mark, iteration marks or punctuation such as `、` & `。`, nor end with
+/// opening brackets. Runs of `…`, `‥` & `―` are not broken. Such characters
+/// are pushed to the next line with the one before them instead.
+///
+/// Combine with
+/// [`ShapedLayout::hanging_punctuation`](struct.ShapedLayout.html#method.hanging_punctuation)
+/// to let full stops & commas hang past the end of lines.
+///
+/// ```
+/// # use luminance_glyph::{KinsokuLineBreaker, Layout};
+/// let layout = Layout::default_wrap().line_breaker(KinsokuLineBreaker);
+/// ```
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct KinsokuLineBreaker;
+
+impl LineBreaker for KinsokuLineBreaker {
+    fn line_breaks<'a>(&self, text: &'a str) -> Box<dyn Iterator<Item = LineBreak> + 'a> {
+        Box::new(
+            BuiltInLineBreaker::default()
+                .line_breaks(text)
+                .filter(move |line_break| match line_break {
+                    LineBreak::Soft(offset) => allows_break(text, *offset),
+                    LineBreak::Hard(_) => true,
+                }),
+        )
+    }
+}
+
+/// Returns whether the text may be broken at `offset` under kinsoku rules.
+fn allows_break(text: &str, offset: usize) -> bool {
+    let before = text[..offset].chars().next_back();
+    let after = text[offset..].chars().next();
+
+    match (before, after) {
+        (Some(before), Some(after)) => {
+            let prohibited = is_line_end_prohibited(before)
+                || is_line_start_prohibited(after)
+                || (before == after && is_inseparable(before));
+            !prohibited
+        }
+        _ => true,
+    }
+}
+
+/// Characters which may not start a line (gyōtō kinsoku).
+fn is_line_start_prohibited(c: char) -> bool {
+    matches!(
+        c,
+        // closing brackets & quotes
+        ')' | ']' | '}' | '»' | '’' | '”' | '〉' | '》' | '」' | '』' | '】' | '〕' | '〗'
+            | '〙' | '〛' | '〞' | '〟' | '）' | '］' | '｝' | '｠' | '｣'
+            // hyphens
+            | '‐' | '–' | '〜' | '゠' | '～'
+            // dividing punctuation
+            | '!' | '?' | '‼' | '⁇' | '⁈' | '⁉' | '！' | '？'
+            // middle dots
+            | '・' | ':' | ';' | '：' | '；' | '･'
+            // full stops & commas
+            | '.' | ',' | '。' | '、' | '．' | '，' | '｡' | '､'
+            // iteration marks
+            | 'ヽ' | 'ヾ' | 'ゝ' | 'ゞ' | '々' | '〻'
+            // prolonged sound marks
+            | 'ー' | 'ｰ'
+            // small kana
+            | 'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' | 'っ' | 'ゃ' | 'ゅ' | 'ょ' | 'ゎ' | 'ゕ' | 'ゖ'
+            | 'ァ' | 'ィ' | 'ゥ' | 'ェ' | 'ォ' | 'ッ' | 'ャ' | 'ュ' | 'ョ' | 'ヮ' | 'ヵ' | 'ヶ'
+            | 'ㇰ'..='ㇿ' | 'ｧ'..='ｯ'
+    )
+}
+
+/// Characters which may not end a line (gyōmatsu kinsoku).
+fn is_line_end_prohibited(c: char) -> bool {
+    matches!(
+        c,
+        // opening brackets & quotes
+        '(' | '['
+            | '{'
+            | '«'
+            | '‘'
+            | '“'
+            | '〈'
+            | '《'
+            | '「'
+            | '『'
+            | '【'
+            | '〔'
+            | '〖'
+            | '〘'
+            | '〚'
+            | '〝'
+            | '（'
+            | '［'
+            | '｛'
+            | '｟'
+            | '｢'
+    )
+}
+
+/// Characters which may not be broken between when repeated.
+#[inline]
+fn is_inseparable(c: char) -> bool {
+    matches!(c, '…' | '‥' | '―' | '—')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn soft_breaks(text: &str) -> Vec<usize> {
+        KinsokuLineBreaker
+            .line_breaks(text)
+            .filter_map(|line_break| match line_break {
+                LineBreak::Soft(offset) => Some(offset),
+                LineBreak::Hard(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn filters_builtin_breaks() {
+        let builtin: Vec<_> = BuiltInLineBreaker::default()
+            .line_breaks("あ～い")
+            .collect();
+        assert_eq!(
+            builtin,
+            [LineBreak::Soft(3), LineBreak::Soft(6), LineBreak::Soft(9)]
+        );
+        // the wave dash may not start a line
+        assert_eq!(soft_breaks("あ～い"), [6, 9]);
+        assert_eq!(soft_breaks("日本語です。"), [3, 6, 9, 12, 18]);
+    }
+
+    #[test]
+    fn keeps_hard_breaks() {
+        let breaks: Vec<_> = KinsokuLineBreaker.line_breaks("あ\n。").collect();
+        assert!(breaks.contains(&LineBreak::Hard(4)));
+    }
+
+    #[test]
+    fn prohibits_line_starts() {
+        for text in [
+            "あ。", "あ、", "あ」", "あっ", "あー", "あゝ", "あ！", "あ・",
+        ] {
+            assert!(!allows_break(text, 3), "{}", text);
+        }
+        assert!(allows_break("あい", 3));
+    }
+
+    #[test]
+    fn prohibits_line_ends() {
+        for text in ["「あ", "（あ", "【あ", "“a"] {
+            let offset = text.chars().next().unwrap().len_utf8();
+            assert!(!allows_break(text, offset), "{}", text);
+        }
+    }
+
+    #[test]
+    fn keeps_runs_of_ellipses_and_dashes() {
+        assert!(!allows_break("……", 3));
+        assert!(!allows_break("――", 3));
+        assert!(allows_break("…―", 3));
+        assert!(allows_break("…", 0));
+        assert!(allows_break("…", 3));
+    }
+}
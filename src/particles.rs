@@ -0,0 +1,404 @@
+use crate::{
+    ab_glyph::{Font, PxScale},
+    GlyphBrush, GlyphBrushBackend, SectionTransform,
+};
+use core::hash::BuildHasher;
+use glyph_brush::{FontId, HorizontalAlign, Layout, Section, Text, VerticalAlign};
+use std::f32::consts::PI;
+
+/// Easing curve of an animation, mapping its progress from `0` to `1`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Accelerating from zero velocity.
+    QuadIn,
+    /// Decelerating to zero velocity.
+    QuadOut,
+    /// Accelerating until halfway, then decelerating.
+    QuadInOut,
+    /// Decelerating to zero velocity, sharper than `QuadOut`.
+    CubicOut,
+    /// Overshooting the end, then settling back.
+    BackOut,
+    /// Bouncing off the end a few times.
+    BounceOut,
+    /// Oscillating about the end with decreasing amplitude.
+    ElasticOut,
+}
+
+impl Easing {
+    /// Returns the eased progress at `t`, clamped to `0..=1`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => t * (2.0 - t),
+            Easing::QuadInOut if t < 0.5 => 2.0 * t * t,
+            Easing::QuadInOut => 1.0 - (2.0 - 2.0 * t).powi(2) / 2.0,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::BackOut => {
+                const OVERSHOOT: f32 = 1.70158;
+                let t = t - 1.0;
+                1.0 + t * t * ((OVERSHOOT + 1.0) * t + OVERSHOOT)
+            }
+            Easing::BounceOut => {
+                const N: f32 = 7.5625;
+                const D: f32 = 2.75;
+                match t {
+                    t if t < 1.0 / D => N * t * t,
+                    t if t < 2.0 / D => N * (t - 1.5 / D).powi(2) + 0.75,
+                    t if t < 2.5 / D => N * (t - 2.25 / D).powi(2) + 0.9375,
+                    t => N * (t - 2.625 / D).powi(2) + 0.984375,
+                }
+            }
+            Easing::ElasticOut => match t {
+                t if t <= 0.0 || t >= 1.0 => t,
+                t => 2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0,
+            },
+        }
+    }
+}
+
+/// Animation of spawned [`TextParticles`](struct.TextParticles.html).
+///
+/// Over its lifetime a particle moves by `offset`, scales from `scale.0` to
+/// `scale.1` about its position & fades out, each along an easing curve.
+///
+/// ```
+/// # use luminance_glyph::{Easing, ParticleStyle};
+/// let damage = ParticleStyle::default()
+///     .lifetime(0.8)
+///     .offset(0.0, -60.0)
+///     .scale(1.8, 1.0)
+///     .scale_easing(Easing::BackOut);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParticleStyle {
+    /// Lifetime in seconds.
+    pub lifetime: f32,
+    /// Movement in pixels over the lifetime.
+    pub offset: (f32, f32),
+    pub motion: Easing,
+    /// Scale factors at spawn & expiry.
+    pub scale: (f32, f32),
+    pub scale_easing: Easing,
+    /// Fraction of the lifetime after which the particle fades out.
+    pub fade_start: f32,
+    pub fade: Easing,
+}
+
+impl Default for ParticleStyle {
+    #[inline]
+    fn default() -> Self {
+        ParticleStyle {
+            lifetime: 1.0,
+            offset: (0.0, -40.0),
+            motion: Easing::QuadOut,
+            scale: (1.0, 1.0),
+            scale_easing: Easing::BackOut,
+            fade_start: 0.5,
+            fade: Easing::QuadIn,
+        }
+    }
+}
+
+impl ParticleStyle {
+    /// Returns an identical `ParticleStyle` with a new lifetime in seconds.
+    #[inline]
+    pub fn lifetime(self, lifetime: f32) -> Self {
+        ParticleStyle { lifetime, ..self }
+    }
+
+    /// Returns an identical `ParticleStyle` with a new movement in pixels.
+    #[inline]
+    pub fn offset(self, x: f32, y: f32) -> Self {
+        ParticleStyle {
+            offset: (x, y),
+            ..self
+        }
+    }
+
+    /// Returns an identical `ParticleStyle` with a new movement easing.
+    #[inline]
+    pub fn motion(self, motion: Easing) -> Self {
+        ParticleStyle { motion, ..self }
+    }
+
+    /// Returns an identical `ParticleStyle` with new scale factors at spawn
+    /// & expiry.
+    #[inline]
+    pub fn scale(self, from: f32, to: f32) -> Self {
+        ParticleStyle {
+            scale: (from, to),
+            ..self
+        }
+    }
+
+    /// Returns an identical `ParticleStyle` with a new scale easing.
+    #[inline]
+    pub fn scale_easing(self, scale_easing: Easing) -> Self {
+        ParticleStyle {
+            scale_easing,
+            ..self
+        }
+    }
+
+    /// Returns an identical `ParticleStyle` fading out from a fraction of
+    /// the lifetime along an easing curve.
+    #[inline]
+    pub fn fade(self, fade_start: f32, fade: Easing) -> Self {
+        ParticleStyle {
+            fade_start,
+            fade,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Particle {
+    text: String,
+    position: (f32, f32),
+    px_scale: PxScale,
+    font_id: FontId,
+    color: [f32; 4],
+    z: f32,
+    style: ParticleStyle,
+    age: f32,
+}
+
+impl Particle {
+    /// Returns the section of the particle at its age, centered on its
+    /// position, with its scale transform.
+    fn section(&self) -> (Section<'_>, SectionTransform) {
+        let style = &self.style;
+        let t = match style.lifetime {
+            lifetime if lifetime > 0.0 => self.age / lifetime,
+            _ => 1.0,
+        };
+
+        let motion = style.motion.apply(t);
+        let position = (
+            self.position.0 + style.offset.0 * motion,
+            self.position.1 + style.offset.1 * motion,
+        );
+
+        let (from, to) = style.scale;
+        let scale = from + (to - from) * style.scale_easing.apply(t);
+
+        let fade = match style.fade_start {
+            start if start < 1.0 => style.fade.apply((t - start) / (1.0 - start)),
+            _ => 0.0,
+        };
+        let mut color = self.color;
+        color[3] *= 1.0 - fade;
+
+        let section = Section::default()
+            .with_screen_position(position)
+            .with_layout(
+                Layout::default_single_line()
+                    .h_align(HorizontalAlign::Center)
+                    .v_align(VerticalAlign::Center),
+            )
+            .add_text(
+                Text::new(&self.text)
+                    .with_scale(self.px_scale)
+                    .with_font_id(self.font_id)
+                    .with_color(color)
+                    .with_z(self.z),
+            );
+        (section, SectionTransform::default().scale(scale, scale))
+    }
+}
+
+/// Short-lived animated texts, e.g. floating combat text, which rise, pop
+/// & fade out, see [`ParticleStyle`](struct.ParticleStyle.html).
+///
+/// Particles are advanced with [`update`](#method.update) and queued to a
+/// `GlyphBrush` each frame with [`queue`](#method.queue), scaled by their
+/// [`SectionTransform`](struct.SectionTransform.html) so that their glyphs
+/// are rasterized once. Expired particles' strings are pooled for the next
+/// spawns.
+///
+/// ```
+/// # use luminance_glyph::{ParticleStyle, Text, TextParticles};
+/// let mut particles = TextParticles::new();
+/// particles.spawn(
+///     Text::new("-42").with_scale(28.0).with_color([1.0, 0.3, 0.2, 1.0]),
+///     (400.0, 300.0),
+///     ParticleStyle::default().scale(1.8, 1.0),
+/// );
+///
+/// // each frame, before queuing them with `particles.queue(&mut glyph_brush)`
+/// particles.update(1.0 / 60.0);
+/// ```
+#[derive(Debug, Default)]
+pub struct TextParticles {
+    particles: Vec<Particle>,
+    pool: Vec<String>,
+}
+
+impl TextParticles {
+    #[inline]
+    pub fn new() -> Self {
+        TextParticles::default()
+    }
+
+    /// Spawns a particle of the text, centered on a position in pixels.
+    pub fn spawn(&mut self, text: Text<'_>, position: (f32, f32), style: ParticleStyle) {
+        let mut string = self.pool.pop().unwrap_or_default();
+        string.push_str(text.text);
+
+        self.particles.push(Particle {
+            text: string,
+            position,
+            px_scale: text.scale,
+            font_id: text.font_id,
+            color: text.extra.color,
+            z: text.extra.z,
+            style,
+            age: 0.0,
+        });
+    }
+
+    /// Advances the particles by `dt` seconds, expiring those past their
+    /// lifetime.
+    pub fn update(&mut self, dt: f32) {
+        let pool = &mut self.pool;
+        self.particles.retain_mut(|particle| {
+            particle.age += dt;
+            let alive = particle.age < particle.style.lifetime;
+            if !alive {
+                let mut string = std::mem::take(&mut particle.text);
+                string.clear();
+                pool.push(string);
+            }
+            alive
+        });
+    }
+
+    /// Queues the active particles to be drawn by the next draw call of the
+    /// `GlyphBrush`, in spawn order.
+    pub fn queue<B, F, H>(&self, glyph_brush: &mut GlyphBrush<B, F, H>)
+    where
+        B: GlyphBrushBackend,
+        F: Font,
+        H: BuildHasher,
+    {
+        for particle in &self.particles {
+            let (section, transform) = particle.section();
+            glyph_brush.queue_transformed(section, transform);
+        }
+    }
+
+    /// Returns the number of active particles.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Expires all particles.
+    pub fn clear(&mut self) {
+        self.update(f32::INFINITY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn(particles: &mut TextParticles, text: &str, lifetime: f32) {
+        particles.spawn(
+            Text::new(text).with_color([1.0; 4]),
+            (100.0, 100.0),
+            ParticleStyle::default().lifetime(lifetime),
+        );
+    }
+
+    #[test]
+    fn expires_particles_past_their_lifetime() {
+        let mut particles = TextParticles::new();
+        spawn(&mut particles, "short", 0.5);
+        spawn(&mut particles, "long", 1.0);
+
+        particles.update(0.25);
+        assert_eq!(particles.len(), 2);
+        particles.update(0.25);
+        assert_eq!(particles.len(), 1);
+        assert_eq!(particles.particles[0].text, "long");
+
+        particles.clear();
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn pools_the_strings_of_expired_particles() {
+        let mut particles = TextParticles::new();
+        spawn(&mut particles, "-1000", 0.5);
+        let allocation = particles.particles[0].text.as_ptr();
+
+        particles.update(1.0);
+        assert_eq!(particles.pool.len(), 1);
+        assert!(particles.pool[0].is_empty());
+
+        spawn(&mut particles, "-42", 0.5);
+        assert!(particles.pool.is_empty());
+        assert_eq!(particles.particles[0].text, "-42");
+        assert_eq!(particles.particles[0].text.as_ptr(), allocation);
+    }
+
+    #[test]
+    fn animates_along_the_style() {
+        let style = ParticleStyle::default()
+            .lifetime(2.0)
+            .offset(0.0, -40.0)
+            .motion(Easing::Linear)
+            .scale(2.0, 1.0)
+            .scale_easing(Easing::Linear)
+            .fade(0.5, Easing::Linear);
+        let mut particles = TextParticles::new();
+        particles.spawn(Text::new("+5").with_color([1.0; 4]), (10.0, 20.0), style);
+
+        let at = |particles: &TextParticles| {
+            let (section, transform) = particles.particles[0].section();
+            (
+                section.screen_position,
+                transform.scale.0,
+                section.text[0].extra.color[3],
+            )
+        };
+        assert_eq!(at(&particles), ((10.0, 20.0), 2.0, 1.0));
+        // fading from halfway
+        particles.update(1.0);
+        assert_eq!(at(&particles), ((10.0, 0.0), 1.5, 1.0));
+        particles.update(0.5);
+        assert_eq!(at(&particles), ((10.0, -10.0), 1.25, 0.5));
+    }
+
+    #[test]
+    fn eases_from_zero_to_one() {
+        let easings = [
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicOut,
+            Easing::BackOut,
+            Easing::BounceOut,
+            Easing::ElasticOut,
+        ];
+        for easing in &easings {
+            assert!(easing.apply(-1.0).abs() < 1e-5, "{:?}", easing);
+            assert!((easing.apply(2.0) - 1.0).abs() < 1e-5, "{:?}", easing);
+        }
+        assert_eq!(Easing::QuadInOut.apply(0.5), 0.5);
+        assert!(Easing::BackOut.apply(0.7) > 1.0);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::{
+    ab_glyph::{point, Font, Point, ScaleFont},
+    layout::rotated_about_center,
+};
+use glyph_brush::{HorizontalAlign, SectionGlyph};
+
+/// Maximum length in pixels of the line segments curves are flattened into.
+const FLATTENING: f32 = 2.0;
+
+/// A path text is laid out along by
+/// [`GlyphBrush::queue_on_path`](struct.GlyphBrush.html#method.queue_on_path),
+/// made of lines, Bézier curves & circular arcs in screen coordinates.
+///
+/// ```
+/// # use luminance_glyph::TextPath;
+/// let river = TextPath::new((20.0, 300.0))
+///     .cubic_to((120.0, 200.0), (240.0, 400.0), (380.0, 260.0))
+///     .line_to((500.0, 240.0));
+///
+/// // clockwise from the top of the circle
+/// let badge = TextPath::arc((200.0, 200.0), 80.0, -std::f32::consts::FRAC_PI_2, 3.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextPath {
+    points: Vec<Point>,
+    /// Arc length from the start of the path to each point.
+    distances: Vec<f32>,
+}
+
+impl TextPath {
+    /// Starts a path at a point.
+    pub fn new(start: (f32, f32)) -> Self {
+        TextPath {
+            points: vec![point(start.0, start.1)],
+            distances: vec![0.0],
+        }
+    }
+
+    /// A path through each of the points in turn.
+    pub fn polyline(points: &[(f32, f32)]) -> Self {
+        let mut path = TextPath {
+            points: Vec::with_capacity(points.len()),
+            distances: Vec::with_capacity(points.len()),
+        };
+        for p in points {
+            path.push(point(p.0, p.1));
+        }
+        path
+    }
+
+    /// A circular arc around `center`, from `start_angle` sweeping
+    /// `sweep_angle` radians, clockwise on screen when positive.
+    ///
+    /// Text runs along the outside of clockwise arcs, reading left to right
+    /// over the top of a circle.
+    pub fn arc(center: (f32, f32), radius: f32, start_angle: f32, sweep_angle: f32) -> Self {
+        let steps = segments((sweep_angle * radius).abs());
+        let at = |angle: f32| {
+            let (sin, cos) = angle.sin_cos();
+            point(center.0 + radius * cos, center.1 + radius * sin)
+        };
+
+        let mut path = TextPath::polyline(&[]);
+        for step in 0..=steps {
+            path.push(at(start_angle + sweep_angle * step as f32 / steps as f32));
+        }
+        path
+    }
+
+    /// Adds a line to a point.
+    pub fn line_to(mut self, to: (f32, f32)) -> Self {
+        self.push(point(to.0, to.1));
+        self
+    }
+
+    /// Adds a quadratic Bézier curve to a point.
+    pub fn quad_to(mut self, control: (f32, f32), to: (f32, f32)) -> Self {
+        let p0 = self.end();
+        let (p1, p2) = (point(control.0, control.1), point(to.0, to.1));
+
+        let steps = segments(distance(p0, p1) + distance(p1, p2));
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            let u = 1.0 - t;
+            self.push(scale(p0, u * u) + scale(p1, 2.0 * u * t) + scale(p2, t * t));
+        }
+        self
+    }
+
+    /// Adds a cubic Bézier curve to a point.
+    pub fn cubic_to(mut self, control1: (f32, f32), control2: (f32, f32), to: (f32, f32)) -> Self {
+        let p0 = self.end();
+        let (p1, p2, p3) = (
+            point(control1.0, control1.1),
+            point(control2.0, control2.1),
+            point(to.0, to.1),
+        );
+
+        let steps = segments(distance(p0, p1) + distance(p1, p2) + distance(p2, p3));
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            let u = 1.0 - t;
+            self.push(
+                scale(p0, u * u * u)
+                    + scale(p1, 3.0 * u * u * t)
+                    + scale(p2, 3.0 * u * t * t)
+                    + scale(p3, t * t * t),
+            );
+        }
+        self
+    }
+
+    /// Returns the arc length of the path.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Returns the point at an arc length from the start of the path, with
+    /// the direction of the path there, or `None` beyond its ends.
+    pub fn point_at(&self, distance: f32) -> Option<((f32, f32), (f32, f32))> {
+        let (p, tangent) = self.at(distance)?;
+        Some(((p.x, p.y), (tangent.x, tangent.y)))
+    }
+
+    fn at(&self, distance: f32) -> Option<(Point, Point)> {
+        if self.points.len() < 2 || !(0.0..=self.length()).contains(&distance) {
+            return None;
+        }
+
+        // the segment holding the distance, skipping zero length ones
+        let end = self
+            .distances
+            .partition_point(|d| *d < distance)
+            .clamp(1, self.points.len() - 1);
+        let start = end - 1;
+
+        let (p0, p1) = (self.points[start], self.points[end]);
+        let length = self.distances[end] - self.distances[start];
+        let t = (distance - self.distances[start]) / length;
+
+        Some((p0 + scale(p1 - p0, t), scale(p1 - p0, 1.0 / length)))
+    }
+
+    #[inline]
+    fn end(&self) -> Point {
+        self.points.last().copied().unwrap_or_default()
+    }
+
+    fn push(&mut self, p: Point) {
+        match self.points.last() {
+            Some(&last) => {
+                let length = distance(last, p);
+                if length > 0.0 {
+                    self.distances.push(self.length() + length);
+                    self.points.push(p);
+                }
+            }
+            None => {
+                self.distances.push(0.0);
+                self.points.push(p);
+            }
+        }
+    }
+}
+
+#[inline]
+fn scale(p: Point, factor: f32) -> Point {
+    point(p.x * factor, p.y * factor)
+}
+
+#[inline]
+fn distance(a: Point, b: Point) -> f32 {
+    let d = b - a;
+    (d.x * d.x + d.y * d.y).sqrt()
+}
+
+/// Returns how many line segments to flatten a curve of about this length
+/// into.
+#[inline]
+fn segments(length: f32) -> usize {
+    ((length / FLATTENING).ceil() as usize).clamp(1, 1024)
+}
+
+/// Places glyphs laid out on a single line starting at the origin along the
+/// path, the line's baseline following it, returning each glyph with its
+/// clockwise rotation. Glyphs beyond the ends of the path are dropped.
+pub(crate) fn place_on_path<F: Font>(
+    fonts: &[F],
+    glyphs: Vec<SectionGlyph>,
+    path: &TextPath,
+    h_align: HorizontalAlign,
+) -> Vec<(SectionGlyph, f32)> {
+    let advance = |sg: &SectionGlyph| {
+        fonts[sg.font_id]
+            .as_scaled(sg.glyph.scale)
+            .h_advance(sg.glyph.id)
+    };
+
+    let baseline = glyphs
+        .iter()
+        .map(|sg| fonts[sg.font_id].as_scaled(sg.glyph.scale).ascent())
+        .fold(0.0, f32::max);
+    let width = glyphs
+        .iter()
+        .map(|sg| sg.glyph.position.x + advance(sg))
+        .fold(0.0, f32::max);
+    let start = match h_align {
+        HorizontalAlign::Left => 0.0,
+        HorizontalAlign::Center => (path.length() - width) / 2.0,
+        HorizontalAlign::Right => path.length() - width,
+    };
+
+    glyphs
+        .into_iter()
+        .filter_map(|mut sg| {
+            // the glyph's center on the baseline follows the path
+            let half_advance = advance(&sg) / 2.0;
+            let (center, tangent) = path.at(start + sg.glyph.position.x + half_advance)?;
+            let normal = point(-tangent.y, tangent.x);
+            let origin = center - scale(tangent, half_advance)
+                + scale(normal, sg.glyph.position.y - baseline);
+
+            let rotation = tangent.y.atan2(tangent.x);
+            sg.glyph = rotated_about_center(&fonts[sg.font_id], sg.glyph, origin, rotation);
+            Some((sg, rotation))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-3 && (actual.1 - expected.1).abs() < 1e-3,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn interpolates_along_segments() {
+        let path = TextPath::polyline(&[(0.0, 0.0), (10.0, 0.0), (10.0, 20.0)]);
+        assert_eq!(path.length(), 30.0);
+
+        let (p, tangent) = path.point_at(5.0).unwrap();
+        assert_near(p, (5.0, 0.0));
+        assert_near(tangent, (1.0, 0.0));
+
+        let (p, tangent) = path.point_at(25.0).unwrap();
+        assert_near(p, (10.0, 15.0));
+        assert_near(tangent, (0.0, 1.0));
+    }
+
+    #[test]
+    fn includes_the_ends() {
+        let path = TextPath::new((0.0, 0.0)).line_to((0.0, -4.0));
+        let (p, tangent) = path.point_at(0.0).unwrap();
+        assert_near(p, (0.0, 0.0));
+        assert_near(tangent, (0.0, -1.0));
+        assert_near(path.point_at(4.0).unwrap().0, (0.0, -4.0));
+
+        assert_eq!(path.point_at(-0.1), None);
+        assert_eq!(path.point_at(4.1), None);
+        assert_eq!(path.point_at(f32::NAN), None);
+    }
+
+    #[test]
+    fn skips_zero_length_segments() {
+        let path = TextPath::polyline(&[(0.0, 0.0), (0.0, 0.0), (3.0, 4.0), (3.0, 4.0)]);
+        assert_eq!(path.length(), 5.0);
+        let (p, tangent) = path.point_at(5.0).unwrap();
+        assert_near(p, (3.0, 4.0));
+        assert_near(tangent, (0.6, 0.8));
+
+        assert_eq!(TextPath::new((1.0, 1.0)).point_at(0.0), None);
+    }
+
+    #[test]
+    fn flattens_arcs_onto_the_circle() {
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let path = TextPath::arc((0.0, 0.0), 100.0, -quarter, quarter);
+        // chords are slightly shorter than the arc
+        assert!((path.length() - 100.0 * quarter).abs() < 0.1);
+
+        let (start, tangent) = path.point_at(0.0).unwrap();
+        assert_near(start, (0.0, -100.0));
+        assert!(tangent.0 > 0.99);
+        let (end, _) = path.point_at(path.length()).unwrap();
+        assert_near(end, (100.0, 0.0));
+    }
+}
@@ -1,23 +1,34 @@
+mod batch;
 mod cache;
+mod instance;
+mod retained;
+mod stamps;
+mod transforms;
 
-use crate::Region;
-use crate::{
-    ab_glyph::{point, Rect},
-    GlyphBrushBackend,
-};
+use crate::{transform::IDENTITY, GlyphBrushBackend, Region, Stamp};
+use batch::Batch;
 use cache::Cache;
+use retained::Retained;
+use stamps::Stamps;
+use transforms::Transforms;
+
+pub(crate) use batch::TaggedInstance;
+pub use instance::Instance;
+pub(crate) use instance::StampVertex;
+pub(crate) use stamps::StampInstance;
 
+use glyph_brush_draw_cache::DrawCacheBuilder;
 use luminance::{
     blending::{Blending, Equation, Factor},
     context::GraphicsContext,
     pipeline::{Pipeline as LuminancePipeline, PipelineError, TextureBinding},
-    pixel::NormUnsigned,
+    pixel::{Floating, NormUnsigned},
     render_state::RenderState,
     shader::{types::Mat44, Program, Uniform},
     shading_gate::ShadingGate,
-    tess::{Interleaved, Mode, Tess, TessBuilder},
+    tess::TessView,
     texture::Dim2,
-    Semantics, UniformInterface, Vertex,
+    Semantics, UniformInterface,
 };
 
 type VertexIndex = u32;
@@ -27,12 +38,26 @@ where
     B: GlyphBrushBackend,
 {
     program: Program<B, Semantics, (), ShaderInterface>,
-    vertex_array: Option<Tess<B, (), VertexIndex, Instance, Interleaved>>,
+    stamp_program: Program<B, StampSemantics, (), StampInterface>,
+    queued: Batch<B>,
+    retained: Retained<B>,
+    stamps: Stamps<B>,
+    /// Time in seconds animating glyph effects.
+    time: f32,
     cache: Cache<B>,
+    transforms: Transforms<B>,
 }
 
-const VS: &str = include_str!("./shaders/vertex.glsl");
+// both vertex shaders place glyphs with the functions of `glyph.glsl`
+const VS: &str = concat!(
+    include_str!("./shaders/glyph.glsl"),
+    include_str!("./shaders/vertex.glsl")
+);
 const FS: &str = include_str!("./shaders/fragment.glsl");
+const STAMP_VS: &str = concat!(
+    include_str!("./shaders/glyph.glsl"),
+    include_str!("./shaders/stamp_vertex.glsl")
+);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Semantics)]
 pub enum Semantics {
@@ -50,87 +75,119 @@ pub enum Semantics {
     TexRightBottom,
     #[sem(name = "color", repr = "[f32; 4]", wrapper = "VertexColor")]
     Color,
+    #[sem(name = "skew", repr = "f32", wrapper = "Skew")]
+    Skew,
+    #[sem(name = "fade", repr = "[f32; 2]", wrapper = "Fade")]
+    Fade,
+    #[sem(name = "rotation", repr = "f32", wrapper = "Rotation")]
+    Rotation,
+    #[sem(
+        name = "local_transform",
+        repr = "[f32; 4]",
+        wrapper = "LocalTransform"
+    )]
+    LocalTransform,
+    #[sem(name = "pivot", repr = "[f32; 2]", wrapper = "Pivot")]
+    Pivot,
+    #[sem(name = "transform_index", repr = "u32", wrapper = "TransformIndex")]
+    TransformIndex,
+    #[sem(name = "billboard", repr = "u32", wrapper = "BillboardMode")]
+    BillboardMode,
+    #[sem(name = "effect", repr = "u32", wrapper = "EffectId")]
+    Effect,
+    #[sem(name = "effect_params", repr = "[f32; 4]", wrapper = "EffectParams")]
+    EffectParams,
+    #[sem(name = "baseline", repr = "f32", wrapper = "Baseline")]
+    Baseline,
 }
 
-#[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Vertex)]
-#[vertex(sem = "Semantics", instanced = "true")]
-pub struct Instance {
-    pub left_top: LeftTop,
-    pub right_bottom: RightBottom,
-    pub tex_left_top: TexLeftTop,
-    pub tex_right_bottom: TexRightBottom,
-    pub color: VertexColor,
+/// Attributes of stamped sections, in a program of their own so that vertex
+/// attribute locations stay below the guaranteed minimum of 16. Glyph quads
+/// are laid out per vertex, sheared & rotated, and copies per instance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Semantics)]
+pub enum StampSemantics {
+    #[sem(name = "stamp_position", repr = "[f32; 3]", wrapper = "StampPosition")]
+    StampPosition,
+    #[sem(
+        name = "stamp_tex_position",
+        repr = "[f32; 2]",
+        wrapper = "StampTexPosition"
+    )]
+    StampTexPosition,
+    #[sem(name = "stamp_color", repr = "[f32; 4]", wrapper = "StampColor")]
+    StampColor,
+    #[sem(name = "stamp_fade", repr = "[f32; 2]", wrapper = "StampFade")]
+    StampFade,
+    #[sem(name = "stamp_phase", repr = "f32", wrapper = "StampPhase")]
+    StampPhase,
+    #[sem(
+        name = "stamp_local_transform",
+        repr = "[f32; 4]",
+        wrapper = "StampLocalTransform"
+    )]
+    StampLocalTransform,
+    #[sem(name = "stamp_pivot", repr = "[f32; 2]", wrapper = "StampPivot")]
+    StampPivot,
+    #[sem(
+        name = "stamp_transform_index",
+        repr = "u32",
+        wrapper = "StampTransformIndex"
+    )]
+    StampTransformIndex,
+    #[sem(name = "stamp_billboard", repr = "u32", wrapper = "StampBillboard")]
+    StampBillboard,
+    #[sem(name = "stamp_effect", repr = "u32", wrapper = "StampEffect")]
+    StampEffect,
+    #[sem(
+        name = "stamp_effect_params",
+        repr = "[f32; 4]",
+        wrapper = "StampEffectParams"
+    )]
+    StampEffectParams,
+    #[sem(name = "copy_offset", repr = "[f32; 2]", wrapper = "CopyOffset")]
+    CopyOffset,
+    #[sem(name = "copy_color", repr = "[f32; 4]", wrapper = "CopyColor")]
+    CopyColor,
+    #[sem(name = "copy_scale", repr = "f32", wrapper = "CopyScale")]
+    CopyScale,
 }
 
-impl Instance {
-    pub fn from_vertex(
-        glyph_brush::GlyphVertex {
-            mut tex_coords,
-            pixel_coords,
-            bounds,
-            extra,
-        }: glyph_brush::GlyphVertex,
-    ) -> Instance {
-        let gl_bounds = bounds;
-
-        let mut gl_rect = Rect {
-            min: point(pixel_coords.min.x as f32, pixel_coords.min.y as f32),
-            max: point(pixel_coords.max.x as f32, pixel_coords.max.y as f32),
-        };
-
-        // handle overlapping bounds, modify uv_rect to preserve texture aspect
-        if gl_rect.max.x > gl_bounds.max.x {
-            let old_width = gl_rect.width();
-            gl_rect.max.x = gl_bounds.max.x;
-            tex_coords.max.x = tex_coords.min.x + tex_coords.width() * gl_rect.width() / old_width;
-        }
-
-        if gl_rect.min.x < gl_bounds.min.x {
-            let old_width = gl_rect.width();
-            gl_rect.min.x = gl_bounds.min.x;
-            tex_coords.min.x = tex_coords.max.x - tex_coords.width() * gl_rect.width() / old_width;
-        }
-
-        if gl_rect.max.y > gl_bounds.max.y {
-            let old_height = gl_rect.height();
-            gl_rect.max.y = gl_bounds.max.y;
-            tex_coords.max.y =
-                tex_coords.min.y + tex_coords.height() * gl_rect.height() / old_height;
-        }
-
-        if gl_rect.min.y < gl_bounds.min.y {
-            let old_height = gl_rect.height();
-            gl_rect.min.y = gl_bounds.min.y;
-            tex_coords.min.y =
-                tex_coords.max.y - tex_coords.height() * gl_rect.height() / old_height;
-        }
-
-        Instance {
-            left_top: LeftTop::from([gl_rect.min.x, gl_rect.max.y, extra.z]),
-            right_bottom: RightBottom::from([gl_rect.max.x, gl_rect.min.y]),
-            tex_left_top: TexLeftTop::from([tex_coords.min.x, tex_coords.max.y]),
-            tex_right_bottom: TexRightBottom::from([tex_coords.max.x, tex_coords.min.y]),
-            color: VertexColor::from(extra.color),
-        }
-    }
+#[derive(UniformInterface)]
+struct ShaderInterface {
+    transform: Uniform<Mat44<f32>>,
+    font_sampler: Uniform<TextureBinding<Dim2, NormUnsigned>>,
+    section_transforms: Uniform<TextureBinding<Dim2, Floating>>,
+    view: Uniform<Mat44<f32>>,
+    projection: Uniform<Mat44<f32>>,
+    time: Uniform<f32>,
 }
 
 #[derive(UniformInterface)]
-struct ShaderInterface {
+struct StampInterface {
     transform: Uniform<Mat44<f32>>,
     font_sampler: Uniform<TextureBinding<Dim2, NormUnsigned>>,
+    section_transforms: Uniform<TextureBinding<Dim2, Floating>>,
+    view: Uniform<Mat44<f32>>,
+    projection: Uniform<Mat44<f32>>,
+    time: Uniform<f32>,
 }
 
 impl<B> Pipeline<B>
 where
     B: GlyphBrushBackend,
 {
-    pub fn new<C>(ctx: &mut C, cache_width: u32, cache_height: u32) -> Self
+    pub fn new<C>(
+        ctx: &mut C,
+        cache_width: u32,
+        cache_height: u32,
+        retained_cache: DrawCacheBuilder,
+    ) -> Self
     where
         C: GraphicsContext<Backend = B>,
     {
         let cache = Cache::new(ctx, cache_width, cache_height);
+        let retained = Retained::new(ctx, retained_cache);
+        let transforms = Transforms::new(ctx, 1);
 
         let program = ctx
             .new_shader_program::<Semantics, (), ShaderInterface>()
@@ -138,10 +195,21 @@ where
             .expect("shader failed to compile")
             .program;
 
+        let stamp_program = ctx
+            .new_shader_program::<StampSemantics, (), StampInterface>()
+            .from_strings(STAMP_VS, None, None, FS)
+            .expect("shader failed to compile")
+            .program;
+
         Pipeline {
             program,
+            stamp_program,
             cache,
-            vertex_array: None,
+            transforms,
+            queued: Batch::new(),
+            retained,
+            stamps: Stamps::new(),
+            time: 0.0,
         }
     }
 
@@ -150,38 +218,113 @@ where
         pipeline: &mut LuminancePipeline<'a, B>,
         shading_gate: &mut ShadingGate<'a, B>,
         transform: [f32; 16],
+        view_projection: Option<([f32; 16], [f32; 16])>,
+        layer: Option<u32>,
         _region: Option<Region>,
     ) -> Result<(), PipelineError>
     where
         B: Sized, // Note: This is likely an oversight in `luminance`, might be removed sometime
     {
-        if let Some(vao) = &self.vertex_array {
-            let bound_texture = pipeline.bind_texture(&mut self.cache.texture)?;
+        let layers = match layer {
+            Some(layer) => vec![layer],
+            None => {
+                let mut layers: Vec<_> = self
+                    .retained
+                    .layers()
+                    .chain(self.queued.layers())
+                    .chain(self.stamps.layers())
+                    .collect();
+                layers.sort_unstable();
+                layers.dedup();
+                layers
+            }
+        };
+        if layers.is_empty() {
+            return Ok(());
+        }
+
+        let bound_texture = pipeline.bind_texture(&mut self.cache.texture)?;
+        let bound_retained = pipeline.bind_texture(&mut self.retained.cache.texture)?;
+        let bound_transforms = pipeline.bind_texture(&mut self.transforms.texture)?;
 
-            // Start shading with our program.
-            shading_gate.shade(&mut self.program, |mut iface, uni, mut rdr_gate| {
+        // Start rendering things with the default render state provided by luminance.
+        let render_state = RenderState::default().set_blending(Blending {
+            equation: Equation::Additive,
+            src: Factor::SrcAlpha,
+            dst: Factor::SrcAlphaComplement,
+        });
+
+        let time = self.time;
+        let (view, projection) = view_projection.unwrap_or((IDENTITY, IDENTITY));
+
+        // each layer draws its retained texts, queued sections & stamped
+        // copies, in that order
+        for layer in layers {
+            let batches: Vec<_> = self
+                .retained
+                .buffers
+                .get(&layer)
+                .map(|tess| (TessView::whole(tess), &bound_retained))
+                .into_iter()
+                .chain(self.queued.view(layer).map(|view| (view, &bound_texture)))
+                .collect();
+
+            if !batches.is_empty() {
+                // Start shading with our program.
+                shading_gate.shade(&mut self.program, |mut iface, uni, mut rdr_gate| {
+                    iface.set(&uni.transform, to_4x4(&transform));
+                    iface.set(&uni.section_transforms, bound_transforms.binding());
+                    iface.set(&uni.view, to_4x4(&view));
+                    iface.set(&uni.projection, to_4x4(&projection));
+                    iface.set(&uni.time, time);
+
+                    for (view, font_sampler) in batches {
+                        iface.set(&uni.font_sampler, font_sampler.binding());
+                        rdr_gate.render(&render_state, |mut tess_gate| tess_gate.render(view))?;
+                    }
+                    Ok(())
+                })?;
+            }
+
+            let stamped: Vec<_> = self.stamps.views(Some(layer)).collect();
+            if stamped.is_empty() {
+                continue;
+            }
+
+            // stamped sections, an instance per copy
+            shading_gate.shade(&mut self.stamp_program, |mut iface, uni, mut rdr_gate| {
                 iface.set(&uni.transform, to_4x4(&transform));
                 iface.set(&uni.font_sampler, bound_texture.binding());
+                iface.set(&uni.section_transforms, bound_transforms.binding());
+                iface.set(&uni.view, to_4x4(&view));
+                iface.set(&uni.projection, to_4x4(&projection));
+                iface.set(&uni.time, time);
 
-                // Start rendering things with the default render state provided by luminance.
-                rdr_gate.render(
-                    &RenderState::default().set_blending(Blending {
-                        equation: Equation::Additive,
-                        src: Factor::SrcAlpha,
-                        dst: Factor::SrcAlphaComplement,
-                    }),
-                    |mut tess_gate| tess_gate.render(vao),
-                )
-            })
-        } else {
-            Ok(())
+                for view in stamped {
+                    rdr_gate.render(&render_state, |mut tess_gate| tess_gate.render(view))?;
+                }
+                Ok(())
+            })?;
         }
+
+        Ok(())
+    }
+
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
     }
 
     pub fn update_cache(&mut self, offset: [u16; 2], size: [u16; 2], data: &[u8]) {
         self.cache.update(offset, size, data);
     }
 
+    pub fn update_transforms<C>(&mut self, ctx: &mut C, transforms: &[[f32; 16]])
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        self.transforms.update(ctx, transforms);
+    }
+
     pub fn increase_cache_size<C>(&mut self, ctx: &mut C, width: u32, height: u32)
     where
         C: GraphicsContext<Backend = B>,
@@ -189,18 +332,30 @@ where
         self.cache = Cache::new(ctx, width, height);
     }
 
-    pub fn upload<C>(&mut self, ctx: &mut C, instances: &[Instance])
+    /// Uploads the instances of queued sections, stamped ones being drawn
+    /// from their glyph quads.
+    pub fn upload<C>(&mut self, ctx: &mut C, instances: Vec<TaggedInstance>)
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        let (stamped, queued) = instances.into_iter().partition(|t| t.stamp > 0);
+        self.queued.upload(ctx, queued);
+        self.stamps.set_glyphs(stamped);
+    }
+
+    /// Returns the instances & glyph cache of retained texts.
+    #[inline]
+    pub fn retained(&mut self) -> &mut Retained<B> {
+        &mut self.retained
+    }
+
+    /// Uploads the copies of stamped sections, with their section's
+    /// `screen_position`.
+    pub fn upload_stamps<C>(&mut self, ctx: &mut C, copies: &[(Vec<Stamp>, (f32, f32))])
     where
         C: GraphicsContext<Backend = B>,
     {
-        self.vertex_array = Some(
-            TessBuilder::new(ctx)
-                .set_instances(instances)
-                .set_render_vertex_nb(4)
-                .set_mode(Mode::TriangleStrip)
-                .build()
-                .unwrap(),
-        );
+        self.stamps.upload(ctx, copies);
     }
 }
 
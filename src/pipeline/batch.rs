@@ -0,0 +1,195 @@
+use super::{Instance, VertexIndex};
+use crate::GlyphBrushBackend;
+use luminance::{
+    context::GraphicsContext,
+    tess::{Interleaved, Mode, Tess, TessBuilder, TessView},
+};
+
+/// Instance of a glyph, with the layer & batch it is drawn in.
+#[derive(Clone, Copy, Debug)]
+pub struct TaggedInstance {
+    pub layer: u32,
+    /// 1-based index of the stamped section it belongs to, `0` if none, see
+    /// `GlyphBrush::queue_stamped`.
+    pub stamp: u32,
+    pub instance: Instance,
+}
+
+type InstanceTess<B> = Tess<B, (), VertexIndex, Instance, Interleaved>;
+
+/// Instances of a layer, in a buffer of their own.
+struct Layer<T> {
+    layer: u32,
+    /// Kept while the layer is empty, to be written over when it is not.
+    tess: Option<T>,
+    /// Instances drawn, the first ones of `tess`.
+    len: usize,
+}
+
+/// Queued instances, in a buffer per layer so that a layer is drawn on its
+/// own.
+pub struct Batch<B>
+where
+    B: GlyphBrushBackend,
+{
+    /// Layers in ascending order.
+    layers: Vec<Layer<InstanceTess<B>>>,
+}
+
+impl<B> Batch<B>
+where
+    B: GlyphBrushBackend,
+{
+    pub fn new() -> Self {
+        Batch { layers: Vec::new() }
+    }
+
+    /// Uploads the instances, writing over the previous ones of their layer
+    /// while they fit its buffer & rebuilding it with room to grow otherwise.
+    pub fn upload<C>(&mut self, ctx: &mut C, tagged: Vec<TaggedInstance>)
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        for (layer, mut instances) in by_layer(&mut self.layers, tagged) {
+            let layer = &mut self.layers[layer];
+            layer.len = instances.len();
+
+            if let Some(tess) = &mut layer.tess {
+                if tess.inst_nb() >= instances.len() {
+                    let mut buffer = tess.instances_mut().unwrap();
+                    buffer[..instances.len()].copy_from_slice(&instances);
+                    continue;
+                }
+            }
+
+            // instances past `len` are never drawn
+            instances.resize(instances.len().next_power_of_two(), instances[0]);
+            layer.tess = Some(
+                TessBuilder::new(ctx)
+                    .set_instances(&instances[..])
+                    .set_render_vertex_nb(4)
+                    .set_mode(Mode::TriangleStrip)
+                    .build()
+                    .unwrap(),
+            );
+        }
+    }
+
+    /// Returns the layers of the instances, in ascending order.
+    pub fn layers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.layers
+            .iter()
+            .filter(|layer| layer.len > 0)
+            .map(|layer| layer.layer)
+    }
+
+    /// Returns the instances of a layer.
+    pub fn view(
+        &self,
+        layer: u32,
+    ) -> Option<TessView<'_, B, (), VertexIndex, Instance, Interleaved>> {
+        let index = self.layers.binary_search_by_key(&layer, |l| l.layer).ok()?;
+        let layer = &self.layers[index];
+        match (&layer.tess, layer.len) {
+            (Some(tess), len) if len > 0 => Some(TessView::inst_whole(tess, len)),
+            _ => None,
+        }
+    }
+}
+
+/// Groups the instances by layer, keeping the queued order within each,
+/// returning them with the index of their layer in `layers`. Layers missing
+/// from `layers` are added in order, those without instances are emptied.
+fn by_layer<T>(
+    layers: &mut Vec<Layer<T>>,
+    mut tagged: Vec<TaggedInstance>,
+) -> Vec<(usize, Vec<Instance>)> {
+    tagged.sort_by_key(|t| t.layer);
+    for layer in layers.iter_mut() {
+        layer.len = 0;
+    }
+
+    tagged
+        .chunk_by(|a, b| a.layer == b.layer)
+        .map(|chunk| {
+            let layer = chunk[0].layer;
+            let index = match layers.binary_search_by_key(&layer, |l| l.layer) {
+                Ok(index) => index,
+                Err(index) => {
+                    layers.insert(
+                        index,
+                        Layer {
+                            layer,
+                            tess: None,
+                            len: 0,
+                        },
+                    );
+                    index
+                }
+            };
+            (index, chunk.iter().map(|t| t.instance).collect())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LeftTop;
+
+    fn tagged(layer: u32, left: f32) -> TaggedInstance {
+        TaggedInstance {
+            layer,
+            stamp: 0,
+            instance: Instance {
+                left_top: LeftTop::from([left, 0.0, 0.0]),
+                ..Instance::degenerate()
+            },
+        }
+    }
+
+    fn lefts(instances: &[Instance]) -> Vec<f32> {
+        instances.iter().map(|i| i.left_top[0]).collect()
+    }
+
+    #[test]
+    fn groups_instances_by_layer_in_queued_order() {
+        let mut layers: Vec<Layer<()>> = Vec::new();
+        let grouped = by_layer(
+            &mut layers,
+            vec![
+                tagged(2, 0.0),
+                tagged(0, 1.0),
+                tagged(2, 2.0),
+                tagged(0, 3.0),
+            ],
+        );
+
+        let grouped: Vec<_> = grouped
+            .iter()
+            .map(|(index, instances)| (layers[*index].layer, lefts(instances)))
+            .collect();
+        assert_eq!(grouped, [(0, vec![1.0, 3.0]), (2, vec![0.0, 2.0])]);
+    }
+
+    #[test]
+    fn keeps_the_buffers_of_layers() {
+        let mut layers: Vec<Layer<()>> = Vec::new();
+        let _ = by_layer(&mut layers, vec![tagged(1, 0.0), tagged(3, 0.0)]);
+        for layer in &mut layers {
+            layer.tess = Some(());
+            layer.len = 1;
+        }
+
+        let grouped = by_layer(&mut layers, vec![tagged(2, 0.0), tagged(3, 0.0)]);
+        let indices: Vec<_> = grouped.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, [1, 2]);
+
+        let layers: Vec<_> = layers
+            .iter()
+            .map(|l| (l.layer, l.tess.is_some(), l.len))
+            .collect();
+        // lengths are set by the upload, emptied layers keep their buffer
+        assert_eq!(layers, [(1, true, 0), (2, false, 0), (3, true, 0)]);
+    }
+}
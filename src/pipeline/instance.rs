@@ -0,0 +1,146 @@
+// the derived `new` of the vertex types takes an argument per attribute
+#![allow(clippy::too_many_arguments)]
+
+use super::{
+    Baseline, BillboardMode, EffectId, EffectParams, Fade, LeftTop, LocalTransform, Pivot,
+    RightBottom, Rotation, Semantics, Skew, StampBillboard, StampColor, StampEffect,
+    StampEffectParams, StampFade, StampLocalTransform, StampPhase, StampPivot, StampPosition,
+    StampSemantics, StampTexPosition, StampTransformIndex, TexLeftTop, TexRightBottom,
+    TransformIndex, VertexColor,
+};
+use crate::ab_glyph::{point, Rect};
+use luminance::Vertex;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Vertex)]
+#[vertex(sem = "Semantics", instanced = "true")]
+pub struct Instance {
+    pub left_top: LeftTop,
+    pub right_bottom: RightBottom,
+    pub tex_left_top: TexLeftTop,
+    pub tex_right_bottom: TexRightBottom,
+    pub color: VertexColor,
+    /// Horizontal shear of synthetic obliques about `baseline`, see
+    /// [`Synthesis::skew`](struct.Synthesis.html#structfield.skew).
+    pub skew: Skew,
+    /// Baseline y in pixels of the glyph.
+    pub baseline: Baseline,
+    pub fade: Fade,
+    pub rotation: Rotation,
+    /// Column-major 2x2 matrix rotating & scaling the quad about `pivot`.
+    pub local_transform: LocalTransform,
+    pub pivot: Pivot,
+    /// Index of the section's 4x4 transform, `0` for the identity.
+    pub transform_index: TransformIndex,
+    /// How the section transform places the quad: `0` in pixels, or in the
+    /// world `1` fixed, `2` facing the camera & `3` at a constant size in
+    /// pixels, see [`Billboard`](enum.Billboard.html).
+    pub billboard: BillboardMode,
+    /// Animation of the glyph: `0` none, `1` wave, `2` shake & `3` rainbow,
+    /// see [`Effect`](enum.Effect.html).
+    pub effect: EffectId,
+    pub effect_params: EffectParams,
+}
+
+impl Instance {
+    /// Returns an instance drawing nothing, its quad having no area.
+    pub(crate) fn degenerate() -> Instance {
+        Instance {
+            left_top: LeftTop::from([0.0; 3]),
+            right_bottom: RightBottom::from([0.0; 2]),
+            tex_left_top: TexLeftTop::from([0.0; 2]),
+            tex_right_bottom: TexRightBottom::from([0.0; 2]),
+            color: VertexColor::from([0.0; 4]),
+            skew: Skew::from(0.0),
+            baseline: Baseline::from(0.0),
+            fade: Fade::from([0.0, 0.0]),
+            rotation: Rotation::from(0.0),
+            local_transform: LocalTransform::from([1.0, 0.0, 0.0, 1.0]),
+            pivot: Pivot::from([0.0, 0.0]),
+            transform_index: TransformIndex::from(0),
+            billboard: BillboardMode::from(0),
+            effect: EffectId::from(0),
+            effect_params: EffectParams::from([0.0; 4]),
+        }
+    }
+
+    pub fn from_vertex(
+        glyph_brush::GlyphVertex {
+            mut tex_coords,
+            pixel_coords,
+            bounds,
+            extra,
+        }: glyph_brush::GlyphVertex,
+    ) -> Instance {
+        let gl_bounds = bounds;
+
+        let mut gl_rect = Rect {
+            min: point(pixel_coords.min.x, pixel_coords.min.y),
+            max: point(pixel_coords.max.x, pixel_coords.max.y),
+        };
+
+        // handle overlapping bounds, modify uv_rect to preserve texture aspect
+        if gl_rect.max.x > gl_bounds.max.x {
+            let old_width = gl_rect.width();
+            gl_rect.max.x = gl_bounds.max.x;
+            tex_coords.max.x = tex_coords.min.x + tex_coords.width() * gl_rect.width() / old_width;
+        }
+
+        if gl_rect.min.x < gl_bounds.min.x {
+            let old_width = gl_rect.width();
+            gl_rect.min.x = gl_bounds.min.x;
+            tex_coords.min.x = tex_coords.max.x - tex_coords.width() * gl_rect.width() / old_width;
+        }
+
+        if gl_rect.max.y > gl_bounds.max.y {
+            let old_height = gl_rect.height();
+            gl_rect.max.y = gl_bounds.max.y;
+            tex_coords.max.y =
+                tex_coords.min.y + tex_coords.height() * gl_rect.height() / old_height;
+        }
+
+        if gl_rect.min.y < gl_bounds.min.y {
+            let old_height = gl_rect.height();
+            gl_rect.min.y = gl_bounds.min.y;
+            tex_coords.min.y =
+                tex_coords.max.y - tex_coords.height() * gl_rect.height() / old_height;
+        }
+
+        Instance {
+            left_top: LeftTop::from([gl_rect.min.x, gl_rect.max.y, extra.z]),
+            right_bottom: RightBottom::from([gl_rect.max.x, gl_rect.min.y]),
+            tex_left_top: TexLeftTop::from([tex_coords.min.x, tex_coords.max.y]),
+            tex_right_bottom: TexRightBottom::from([tex_coords.max.x, tex_coords.min.y]),
+            color: VertexColor::from(extra.color),
+            skew: Skew::from(0.0),
+            baseline: Baseline::from(0.0),
+            fade: Fade::from([0.0, 0.0]),
+            rotation: Rotation::from(0.0),
+            local_transform: LocalTransform::from([1.0, 0.0, 0.0, 1.0]),
+            pivot: Pivot::from([0.0, 0.0]),
+            transform_index: TransformIndex::from(0),
+            billboard: BillboardMode::from(0),
+            effect: EffectId::from(0),
+            effect_params: EffectParams::from([0.0; 4]),
+        }
+    }
+}
+
+/// Corner of a stamped glyph quad, with the attributes of its glyph.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Vertex)]
+#[vertex(sem = "StampSemantics")]
+pub struct StampVertex {
+    pub position: StampPosition,
+    pub tex_position: StampTexPosition,
+    pub color: StampColor,
+    pub fade: StampFade,
+    /// Phase of the glyph's effect, its quad's left edge.
+    pub phase: StampPhase,
+    pub local_transform: StampLocalTransform,
+    pub pivot: StampPivot,
+    pub transform_index: StampTransformIndex,
+    pub billboard: StampBillboard,
+    pub effect: StampEffect,
+    pub effect_params: StampEffectParams,
+}
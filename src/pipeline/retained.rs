@@ -0,0 +1,330 @@
+use super::{Cache, Instance, VertexIndex};
+use crate::{
+    ab_glyph::{Font, Glyph},
+    GlyphBrushBackend, TextHandle,
+};
+use glyph_brush::SectionGlyph;
+use glyph_brush_draw_cache::{CachedBy, DrawCache, DrawCacheBuilder, TextureCoords};
+use log::warn;
+use luminance::{
+    context::GraphicsContext,
+    tess::{Interleaved, Mode, Tess, TessBuilder},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    mem,
+};
+
+/// Instance range of a retained text, with room to grow in place.
+struct Slot {
+    start: usize,
+    capacity: usize,
+    instances: Vec<Instance>,
+}
+
+/// Slots of retained texts by layer & handle, with the writes & rebuilds of
+/// the layer buffers they need.
+#[derive(Default)]
+struct Slots {
+    slots: BTreeMap<(u32, TextHandle), Slot>,
+    layers: HashMap<TextHandle, u32>,
+    /// Slots to write in place by the next upload, by layer & start.
+    writes: Vec<(u32, usize, Vec<Instance>)>,
+    /// Layers whose slots were added or moved, rebuilt on the next upload.
+    rebuild: BTreeSet<u32>,
+}
+
+impl Slots {
+    /// Replaces the instances of a text, in place if they fit its slot.
+    fn set(&mut self, handle: TextHandle, layer: u32, instances: Vec<Instance>) {
+        if let Some(slot) = self.slots.get_mut(&(layer, handle)) {
+            if instances.len() <= slot.capacity {
+                if !self.rebuild.contains(&layer) {
+                    self.writes
+                        .push((layer, slot.start, padded(&instances, slot.capacity)));
+                }
+                slot.instances = instances;
+                return;
+            }
+        }
+
+        self.remove(handle);
+        let _ = self.layers.insert(handle, layer);
+        let slot = Slot {
+            start: 0,
+            capacity: instances.len().next_power_of_two(),
+            instances,
+        };
+        let _ = self.slots.insert((layer, handle), slot);
+        let _ = self.rebuild.insert(layer);
+    }
+
+    /// Removes the instances of a text, leaving its slot empty until its
+    /// layer is rebuilt.
+    fn remove(&mut self, handle: TextHandle) {
+        let layer = match self.layers.remove(&handle) {
+            Some(layer) => layer,
+            None => return,
+        };
+        if let Some(slot) = self.slots.remove(&(layer, handle)) {
+            if !self.rebuild.contains(&layer) {
+                self.writes
+                    .push((layer, slot.start, padded(&[], slot.capacity)));
+            }
+        }
+    }
+
+    /// Places the slots of a layer one after another, returning the
+    /// instances of its buffer.
+    fn lay_out(&mut self, layer: u32) -> Vec<Instance> {
+        let mut instances = Vec::new();
+        for slot in self.layer_slots(layer) {
+            slot.start = instances.len();
+            instances.extend(padded(&slot.instances, slot.capacity));
+        }
+        instances
+    }
+
+    fn layer_slots(&mut self, layer: u32) -> impl Iterator<Item = &mut Slot> {
+        self.slots
+            .range_mut((layer, TextHandle(0))..=(layer, TextHandle(u64::MAX)))
+            .map(|(_, slot)| slot)
+    }
+}
+
+/// Instances of retained texts, in a buffer per layer of a slot per text
+/// sorted by handle, their glyphs cached in a texture of their own so that
+/// queued sections don't evict them.
+pub struct Retained<B>
+where
+    B: GlyphBrushBackend,
+{
+    /// Buffers of the layers with retained texts.
+    pub buffers: BTreeMap<u32, Tess<B, (), VertexIndex, Instance, Interleaved>>,
+    pub cache: Cache<B>,
+    draw_cache: DrawCache,
+    slots: Slots,
+}
+
+impl<B> Retained<B>
+where
+    B: GlyphBrushBackend,
+{
+    pub fn new<C>(ctx: &mut C, draw_cache: DrawCacheBuilder) -> Self
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        let draw_cache = draw_cache.build();
+        let (width, height) = draw_cache.dimensions();
+
+        Retained {
+            buffers: BTreeMap::new(),
+            cache: Cache::new(ctx, width, height),
+            draw_cache,
+            slots: Slots::default(),
+        }
+    }
+
+    /// Caches the glyphs of all retained texts, growing the texture up to
+    /// `max_dimension` as needed. Returns whether the texture coordinates of
+    /// previously cached glyphs changed.
+    pub fn cache_glyphs<'a, C, F, I>(
+        &mut self,
+        ctx: &mut C,
+        fonts: &[F],
+        glyphs: I,
+        max_dimension: u32,
+    ) -> bool
+    where
+        C: GraphicsContext<Backend = B>,
+        F: Font + Sync,
+        I: Iterator<Item = &'a SectionGlyph> + Clone,
+    {
+        let mut moved = false;
+        loop {
+            self.draw_cache.clear_queue();
+            for sg in glyphs.clone() {
+                self.draw_cache.queue_glyph(sg.font_id.0, sg.glyph.clone());
+            }
+
+            let cache = &mut self.cache;
+            let cached = self.draw_cache.cache_queued(fonts, |rect, data| {
+                let offset = [rect.min[0] as u16, rect.min[1] as u16];
+                let size = [rect.width() as u16, rect.height() as u16];
+                cache.update(offset, size, data);
+            });
+
+            match cached {
+                Ok(CachedBy::Adding) => return moved,
+                Ok(CachedBy::Reordering) => return true,
+                Err(err) => {
+                    let (width, height) = self.draw_cache.dimensions();
+                    if width >= max_dimension && height >= max_dimension {
+                        warn!("Failed to cache the glyphs of retained texts: {}", err);
+                        return true;
+                    }
+
+                    let (width, height) = (
+                        (width * 2).min(max_dimension),
+                        (height * 2).min(max_dimension),
+                    );
+                    self.draw_cache
+                        .to_builder()
+                        .dimensions(width, height)
+                        .rebuild(&mut self.draw_cache);
+                    self.cache = Cache::new(ctx, width, height);
+                    moved = true;
+                }
+            }
+        }
+    }
+
+    /// Returns the texture & pixel coordinates of a cached glyph.
+    #[inline]
+    pub fn rect_for(&self, font_id: usize, glyph: &Glyph) -> Option<TextureCoords> {
+        self.draw_cache.rect_for(font_id, glyph)
+    }
+
+    /// Replaces the instances of a text, in place if they fit its slot.
+    #[inline]
+    pub fn set(&mut self, handle: TextHandle, layer: u32, instances: Vec<Instance>) {
+        self.slots.set(handle, layer, instances)
+    }
+
+    /// Removes the instances of a text, leaving its slot empty until its
+    /// layer's buffer is rebuilt.
+    #[inline]
+    pub fn remove(&mut self, handle: TextHandle) {
+        self.slots.remove(handle)
+    }
+
+    /// Uploads the slots written since the last upload, rebuilding the
+    /// buffers of layers whose slots were added or moved.
+    pub fn upload<C>(&mut self, ctx: &mut C)
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        let rebuild = mem::take(&mut self.slots.rebuild);
+        for (layer, start, instances) in self.slots.writes.drain(..) {
+            if let (Some(tess), false) = (self.buffers.get_mut(&layer), rebuild.contains(&layer)) {
+                let mut buffer = tess.instances_mut().unwrap();
+                buffer[start..start + instances.len()].copy_from_slice(&instances);
+            }
+        }
+
+        for layer in rebuild {
+            let instances = self.slots.lay_out(layer);
+            if instances.is_empty() {
+                let _ = self.buffers.remove(&layer);
+                continue;
+            }
+
+            let tess = TessBuilder::new(ctx)
+                .set_instances(&instances[..])
+                .set_render_vertex_nb(4)
+                .set_mode(Mode::TriangleStrip)
+                .build()
+                .unwrap();
+            let _ = self.buffers.insert(layer, tess);
+        }
+    }
+
+    /// Returns the layers of retained texts, in ascending order.
+    pub fn layers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.slots.slots.keys().map(|(layer, _)| *layer)
+    }
+}
+
+/// Returns the instances followed by ones drawing nothing, up to `capacity`.
+fn padded(instances: &[Instance], capacity: usize) -> Vec<Instance> {
+    let mut padded = instances.to_vec();
+    padded.resize(capacity, Instance::degenerate());
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LeftTop;
+
+    fn instances(lefts: &[f32]) -> Vec<Instance> {
+        lefts
+            .iter()
+            .map(|left| Instance {
+                left_top: LeftTop::from([*left, 0.0, 0.0]),
+                ..Instance::degenerate()
+            })
+            .collect()
+    }
+
+    /// Returns the layer & start of each write.
+    fn writes(slots: &mut Slots) -> Vec<(u32, usize)> {
+        slots
+            .writes
+            .drain(..)
+            .map(|(layer, start, _)| (layer, start))
+            .collect()
+    }
+
+    /// Lays out the layers to rebuild, as the upload does.
+    fn rebuild(slots: &mut Slots) -> Vec<u32> {
+        let layers: Vec<_> = mem::take(&mut slots.rebuild).into_iter().collect();
+        for layer in &layers {
+            let _ = slots.lay_out(*layer);
+        }
+        slots.writes.clear();
+        layers
+    }
+
+    #[test]
+    fn writes_fitting_instances_in_place() {
+        let mut slots = Slots::default();
+        slots.set(TextHandle(1), 0, instances(&[0.0, 1.0, 2.0]));
+        slots.set(TextHandle(2), 0, instances(&[0.0]));
+        assert_eq!(rebuild(&mut slots), [0]);
+
+        // within the capacity of 4
+        slots.set(TextHandle(1), 0, instances(&[0.0; 4]));
+        slots.set(TextHandle(2), 0, instances(&[5.0]));
+        assert_eq!(writes(&mut slots), [(0, 0), (0, 4)]);
+        assert!(slots.rebuild.is_empty());
+    }
+
+    #[test]
+    fn rebuilds_grown_or_moved_texts() {
+        let mut slots = Slots::default();
+        slots.set(TextHandle(1), 0, instances(&[0.0]));
+        slots.set(TextHandle(2), 1, instances(&[0.0]));
+        let _ = rebuild(&mut slots);
+
+        slots.set(TextHandle(1), 0, instances(&[0.0, 1.0]));
+        assert_eq!(rebuild(&mut slots), [0]);
+
+        // the slot left behind is emptied in place
+        slots.set(TextHandle(2), 0, instances(&[0.0]));
+        assert_eq!(writes(&mut slots), [(1, 0)]);
+        assert_eq!(rebuild(&mut slots), [0]);
+        assert_eq!(slots.layers[&TextHandle(2)], 0);
+    }
+
+    #[test]
+    fn empties_removed_slots() {
+        let mut slots = Slots::default();
+        slots.set(TextHandle(1), 0, instances(&[0.0, 1.0]));
+        slots.set(TextHandle(2), 0, instances(&[0.0, 1.0, 2.0]));
+        let _ = rebuild(&mut slots);
+
+        slots.remove(TextHandle(2));
+        let written: Vec<_> = slots
+            .writes
+            .iter()
+            .map(|(_, start, instances)| (*start, instances.len()))
+            .collect();
+        assert_eq!(written, [(2, 4)]);
+        assert!(slots.writes[0]
+            .2
+            .iter()
+            .all(|i| *i == Instance::degenerate()));
+        assert_eq!(slots.lay_out(0).len(), 2);
+    }
+}
@@ -0,0 +1,291 @@
+use super::{
+    CopyColor, CopyOffset, CopyScale, StampSemantics, StampVertex, TaggedInstance, VertexIndex,
+};
+use crate::{GlyphBrushBackend, Stamp};
+use luminance::{
+    context::GraphicsContext,
+    tess::{Interleaved, Mode, Tess, TessBuilder, TessView},
+    Vertex,
+};
+
+/// Copy of a stamped section, moving & scaling its quads from their layout
+/// positions.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Vertex)]
+#[vertex(sem = "StampSemantics", instanced = "true")]
+pub struct StampInstance {
+    pub offset: CopyOffset,
+    pub color: CopyColor,
+    pub scale: CopyScale,
+}
+
+type StampTess<B> = Tess<B, StampVertex, VertexIndex, StampInstance, Interleaved>;
+
+/// Glyph quads of a stamped section, as laid out, & their copies.
+struct Stamped<T> {
+    layer: u32,
+    vertices: Vec<StampVertex>,
+    indices: Vec<VertexIndex>,
+    /// Quads & copies, built once copies are uploaded & kept while the quads
+    /// don't change.
+    tess: Option<T>,
+    /// Copies drawn this frame, the first instances of `tess`.
+    copies: usize,
+}
+
+impl<T> Stamped<T> {
+    fn new(layer: u32, vertices: Vec<StampVertex>, indices: Vec<VertexIndex>) -> Self {
+        Stamped {
+            layer,
+            vertices,
+            indices,
+            tess: None,
+            copies: 0,
+        }
+    }
+}
+
+/// Stamped sections, drawn with an instance per copy.
+pub struct Stamps<B>
+where
+    B: GlyphBrushBackend,
+{
+    stamped: Vec<Stamped<StampTess<B>>>,
+}
+
+impl<B> Stamps<B>
+where
+    B: GlyphBrushBackend,
+{
+    pub fn new() -> Self {
+        Stamps {
+            stamped: Vec::new(),
+        }
+    }
+
+    /// Replaces the glyph quads, of instances tagged with their 1-based
+    /// stamp index, keeping the buffers of stamped sections whose quads are
+    /// unchanged.
+    #[inline]
+    pub fn set_glyphs(&mut self, instances: Vec<TaggedInstance>) {
+        set_glyphs(&mut self.stamped, instances)
+    }
+
+    /// Uploads the copies of each stamped section, writing them over the
+    /// previous ones while they fit & building its buffers otherwise.
+    pub fn upload<C>(&mut self, ctx: &mut C, copies: &[(Vec<Stamp>, (f32, f32))])
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        for (index, stamped) in self.stamped.iter_mut().enumerate() {
+            stamped.copies = 0;
+            let (stamps, origin) = match copies.get(index) {
+                Some((stamps, origin)) if !stamps.is_empty() => (stamps, origin),
+                _ => continue,
+            };
+            if stamped.vertices.is_empty() {
+                continue;
+            }
+
+            // scaled about the section's position, then moved to the copy's
+            let mut instances: Vec<_> = stamps
+                .iter()
+                .map(|stamp| StampInstance {
+                    offset: [
+                        stamp.position.0 - origin.0 * stamp.scale,
+                        stamp.position.1 - origin.1 * stamp.scale,
+                    ]
+                    .into(),
+                    color: stamp.color.into(),
+                    scale: stamp.scale.into(),
+                })
+                .collect();
+            stamped.copies = instances.len();
+
+            if let Some(tess) = &mut stamped.tess {
+                if tess.inst_nb() >= instances.len() {
+                    if let Ok(mut slice) = tess.instances_mut() {
+                        slice[..instances.len()].copy_from_slice(&instances);
+                        continue;
+                    }
+                }
+            }
+
+            // room for more copies, padding instances not being drawn
+            let capacity = instances.len().next_power_of_two();
+            instances.resize(capacity, instances[0]);
+            let tess = TessBuilder::new(ctx)
+                .set_vertices(&stamped.vertices[..])
+                .set_indices(&stamped.indices[..])
+                .set_instances(&instances[..])
+                .set_mode(Mode::Triangle)
+                .build()
+                .unwrap();
+            stamped.tess = Some(tess);
+        }
+    }
+
+    /// Returns the layers of stamped sections with copies this frame.
+    pub fn layers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.stamped
+            .iter()
+            .filter(|stamped| stamped.copies > 0)
+            .map(|stamped| stamped.layer)
+    }
+
+    /// Returns the stamped sections with copies this frame, in the layer if
+    /// any.
+    pub fn views(
+        &self,
+        layer: Option<u32>,
+    ) -> impl Iterator<Item = TessView<'_, B, StampVertex, VertexIndex, StampInstance, Interleaved>>
+    {
+        self.stamped
+            .iter()
+            .filter(move |stamped| layer.is_none() || layer == Some(stamped.layer))
+            .filter(|stamped| stamped.copies > 0)
+            .filter_map(|stamped| {
+                Some(TessView::inst_whole(stamped.tess.as_ref()?, stamped.copies))
+            })
+    }
+}
+
+/// Replaces the glyph quads of stamped sections, keeping the ones whose quads
+/// are unchanged. Stamp indices without instances, such as sections laid out
+/// empty, get an empty entry in no particular layer, which is never drawn.
+fn set_glyphs<T>(stamped: &mut Vec<Stamped<T>>, instances: Vec<TaggedInstance>) {
+    let mut quads: Vec<Option<Stamped<T>>> = Vec::new();
+    for tagged in instances {
+        let index = tagged.stamp as usize - 1;
+        if quads.len() <= index {
+            quads.resize_with(index + 1, || None);
+        }
+
+        let quads =
+            quads[index].get_or_insert_with(|| Stamped::new(tagged.layer, Vec::new(), Vec::new()));
+        let first = quads.vertices.len() as VertexIndex;
+        quads.vertices.extend(corners(&tagged));
+        quads
+            .indices
+            .extend([0, 1, 2, 2, 1, 3].iter().map(|i| first + i));
+    }
+
+    stamped.truncate(quads.len());
+    for (index, quads) in quads.into_iter().enumerate() {
+        let new = quads.unwrap_or_else(|| Stamped::new(0, Vec::new(), Vec::new()));
+        match stamped.get_mut(index) {
+            Some(old) if old.vertices.is_empty() && new.vertices.is_empty() => {}
+            Some(old) if old.layer == new.layer && old.vertices == new.vertices => {}
+            Some(old) => *old = new,
+            None => stamped.push(new),
+        }
+    }
+}
+
+/// Returns the corners of a glyph quad, in the order of the vertex shader,
+/// sheared & rotated as the vertex shader of queued glyphs does.
+fn corners(tagged: &TaggedInstance) -> [StampVertex; 4] {
+    let instance = &tagged.instance;
+    let [left, top, z] = *instance.left_top;
+    let [right, bottom] = *instance.right_bottom;
+    let [tex_left, tex_top] = *instance.tex_left_top;
+    let [tex_right, tex_bottom] = *instance.tex_right_bottom;
+    let (skew, baseline) = (*instance.skew, *instance.baseline);
+    let (sin, cos) = (*instance.rotation).sin_cos();
+    let center = ((left + right) / 2.0, (top + bottom) / 2.0);
+
+    let corner = |x: f32, y: f32, tex_x: f32, tex_y: f32| {
+        // synthetic oblique shears rightwards above the baseline, y pointing down
+        let x = x + skew * (baseline - y);
+        // rotate clockwise on screen about the quad center
+        let (dx, dy) = (x - center.0, y - center.1);
+        let (x, y) = (
+            center.0 + cos * dx - sin * dy,
+            center.1 + sin * dx + cos * dy,
+        );
+
+        StampVertex {
+            position: [x, y, z].into(),
+            tex_position: [tex_x, tex_y].into(),
+            color: (*instance.color).into(),
+            fade: (*instance.fade).into(),
+            phase: left.into(),
+            local_transform: (*instance.local_transform).into(),
+            pivot: (*instance.pivot).into(),
+            transform_index: (*instance.transform_index).into(),
+            billboard: (*instance.billboard).into(),
+            effect: (*instance.effect).into(),
+            effect_params: (*instance.effect_params).into(),
+        }
+    };
+    [
+        corner(left, top, tex_left, tex_top),
+        corner(right, top, tex_right, tex_top),
+        corner(left, bottom, tex_left, tex_bottom),
+        corner(right, bottom, tex_right, tex_bottom),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Instance, LeftTop};
+
+    fn tagged(stamp: u32, layer: u32, left: f32) -> TaggedInstance {
+        TaggedInstance {
+            layer,
+            stamp,
+            instance: Instance {
+                left_top: LeftTop::from([left, 0.0, 0.0]),
+                ..Instance::degenerate()
+            },
+        }
+    }
+
+    /// Sets the glyphs, marking every stamped section as built.
+    fn set_built(stamped: &mut Vec<Stamped<()>>, instances: Vec<TaggedInstance>) {
+        set_glyphs(stamped, instances);
+        for stamped in stamped {
+            stamped.tess = Some(());
+        }
+    }
+
+    fn built(stamped: &[Stamped<()>]) -> Vec<bool> {
+        stamped.iter().map(|s| s.tess.is_some()).collect()
+    }
+
+    #[test]
+    fn keeps_unchanged_quads() {
+        let mut stamped = Vec::new();
+        set_built(&mut stamped, vec![tagged(1, 0, 0.0), tagged(2, 0, 5.0)]);
+
+        set_glyphs(&mut stamped, vec![tagged(1, 0, 0.0), tagged(2, 0, 6.0)]);
+        assert_eq!(built(&stamped), [true, false]);
+
+        // moved to another layer
+        set_built(&mut stamped, vec![tagged(1, 0, 0.0), tagged(2, 0, 6.0)]);
+        set_glyphs(&mut stamped, vec![tagged(1, 1, 0.0), tagged(2, 0, 6.0)]);
+        assert_eq!(built(&stamped), [false, true]);
+        assert_eq!(stamped[0].layer, 1);
+    }
+
+    #[test]
+    fn drops_sections_no_longer_stamped() {
+        let mut stamped = Vec::new();
+        set_built(&mut stamped, vec![tagged(1, 0, 0.0), tagged(2, 0, 5.0)]);
+        set_glyphs(&mut stamped, vec![tagged(1, 0, 0.0)]);
+        assert_eq!(built(&stamped), [true]);
+    }
+
+    #[test]
+    fn keeps_empty_sections_between_stamped_ones() {
+        let mut stamped = Vec::new();
+        set_built(&mut stamped, vec![tagged(3, 2, 0.0), tagged(1, 0, 5.0)]);
+        assert_eq!(stamped[2].layer, 2);
+        assert!(stamped[1].vertices.is_empty());
+
+        // the empty section is kept whichever stamp is seen first
+        set_glyphs(&mut stamped, vec![tagged(1, 0, 5.0), tagged(3, 2, 0.0)]);
+        assert_eq!(built(&stamped), [true, true, true]);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::transform::IDENTITY;
+use luminance::{
+    backend,
+    context::GraphicsContext,
+    pixel::RGBA32F,
+    texture::{Dim2, MagFilter, MinFilter, Sampler, TexelUpload, Texture, Wrap},
+};
+
+/// Transforms per texture row, 4 texels each, keeping the texture width
+/// within the 1024 texels every GL 3.3 implementation supports.
+const ROW_LEN: u32 = 256;
+
+/// Per-section transforms, stored in a float texture indexed by
+/// `Instance::transform_index`, one matrix column per texel & wrapping to a
+/// new row every `ROW_LEN` transforms. The first one is the identity.
+///
+/// Only the height grows, so that the `ROW_LEN` × 1024 transforms of the
+/// guaranteed texture size fit rather than a single column of 1024.
+pub struct Transforms<B>
+where
+    B: ?Sized + backend::texture::Texture<Dim2, RGBA32F>,
+{
+    pub(crate) texture: Texture<B, Dim2, RGBA32F>,
+    /// Height of the texture, in rows of `ROW_LEN` transforms.
+    rows: u32,
+}
+
+impl<B> Transforms<B>
+where
+    B: ?Sized + backend::texture::Texture<Dim2, RGBA32F>,
+{
+    pub fn new<C>(context: &mut C, rows: u32) -> Self
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        let mut texels = IDENTITY.to_vec();
+        texels.resize((rows * ROW_LEN) as usize * 16, 0.0);
+
+        let texture = context
+            .new_texture_raw(
+                [4 * ROW_LEN, rows],
+                Sampler {
+                    wrap_r: Wrap::ClampToEdge,
+                    wrap_s: Wrap::ClampToEdge,
+                    wrap_t: Wrap::ClampToEdge,
+                    min_filter: MinFilter::Nearest,
+                    mag_filter: MagFilter::Nearest,
+                    depth_comparison: None,
+                },
+                TexelUpload::BaseLevel {
+                    texels: &texels,
+                    mipmaps: 0,
+                },
+            )
+            .expect("failed to create transform texture");
+
+        Transforms { texture, rows }
+    }
+
+    /// Uploads the transforms following the identity, growing the texture
+    /// as needed.
+    pub fn update<C>(&mut self, context: &mut C, transforms: &[[f32; 16]])
+    where
+        C: GraphicsContext<Backend = B>,
+    {
+        if transforms.is_empty() {
+            return;
+        }
+
+        // the identity & the transforms, padded to whole rows
+        let count = transforms.len() as u32 + 1;
+        let rows = count.div_ceil(ROW_LEN);
+        if rows > self.rows {
+            *self = Transforms::new(context, rows.next_power_of_two());
+        }
+
+        let mut texels = IDENTITY.to_vec();
+        texels.extend(transforms.iter().flatten());
+        texels.resize((rows * ROW_LEN) as usize * 16, 0.0);
+        self.texture
+            .upload_part_raw(
+                [0, 0],
+                [4 * ROW_LEN, rows],
+                TexelUpload::BaseLevel {
+                    texels: &texels,
+                    mipmaps: 0,
+                },
+            )
+            .expect("failed to upload transforms");
+    }
+}
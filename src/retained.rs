@@ -0,0 +1,91 @@
+use crate::{ab_glyph::Rect, extra::GlyphExtra};
+use glyph_brush::SectionGlyph;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Handle of a text retained by a `GlyphBrush`, see
+/// [`GlyphBrush::create_text`](struct.GlyphBrush.html#method.create_text).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextHandle(pub(crate) u64);
+
+/// A retained text, laid out once when created or updated.
+#[derive(Debug)]
+pub(crate) struct RetainedText {
+    /// Glyphs of the text, each with its own section index into `extra`.
+    pub glyphs: Vec<SectionGlyph>,
+    pub extra: Vec<GlyphExtra>,
+    pub bounds: Rect,
+    pub layer: u32,
+    pub visible: bool,
+}
+
+/// Texts drawn every frame until removed, in creation order, with the
+/// handles of those changed since their instances were last uploaded.
+#[derive(Debug, Default)]
+pub(crate) struct RetainedTexts {
+    texts: BTreeMap<TextHandle, RetainedText>,
+    changed: BTreeSet<TextHandle>,
+    next: u64,
+}
+
+impl RetainedTexts {
+    pub fn insert(&mut self, text: RetainedText) -> TextHandle {
+        let handle = TextHandle(self.next);
+        self.next += 1;
+        let _ = self.texts.insert(handle, text);
+        let _ = self.changed.insert(handle);
+        handle
+    }
+
+    /// Replaces the text of the handle, keeping its visibility.
+    pub fn replace(&mut self, handle: TextHandle, text: RetainedText) -> bool {
+        match self.texts.get_mut(&handle) {
+            Some(old) => {
+                *old = RetainedText {
+                    visible: old.visible,
+                    ..text
+                };
+                let _ = self.changed.insert(handle);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_visible(&mut self, handle: TextHandle, visible: bool) -> bool {
+        match self.texts.get_mut(&handle) {
+            Some(text) => {
+                if text.visible != visible {
+                    text.visible = visible;
+                    let _ = self.changed.insert(handle);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, handle: TextHandle) -> Option<&RetainedText> {
+        self.texts.get(&handle)
+    }
+
+    pub fn remove(&mut self, handle: TextHandle) -> bool {
+        let removed = self.texts.remove(&handle).is_some();
+        if removed {
+            let _ = self.changed.insert(handle);
+        }
+        removed
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (TextHandle, &RetainedText)> + Clone {
+        self.texts.iter().map(|(handle, text)| (*handle, text))
+    }
+
+    /// Returns the handles of texts created, updated, shown, hidden or
+    /// removed since the last call.
+    #[inline]
+    pub fn take_changed(&mut self) -> BTreeSet<TextHandle> {
+        std::mem::take(&mut self.changed)
+    }
+}
@@ -0,0 +1,126 @@
+use glyph_brush::SectionGlyph;
+
+/// How much of a section a typewriter animation reveals, see
+/// [`GlyphBrush::queue_revealed`](struct.GlyphBrush.html#method.queue_revealed).
+///
+/// Glyphs are revealed in text order, the glyph at the edge fading in over
+/// `fade` glyphs.
+///
+/// ```
+/// # use luminance_glyph::Reveal;
+/// // 0.8s into a dialog line typed at 30 glyphs per second
+/// let reveal = Reveal::time(0.8, 30.0).fade(2.0);
+/// assert_eq!(reveal.glyphs, 24.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reveal {
+    /// Number of revealed glyphs, fractional while one fades in.
+    pub glyphs: f32,
+    /// Number of glyphs over which the edge fades in, `0` to reveal whole
+    /// glyphs at once.
+    pub fade: f32,
+}
+
+impl Reveal {
+    /// Returns a reveal of a number of glyphs, without fading.
+    #[inline]
+    pub fn glyphs(glyphs: f32) -> Self {
+        Reveal { glyphs, fade: 0.0 }
+    }
+
+    /// Returns a reveal at `seconds` into typing at a rate, without fading.
+    #[inline]
+    pub fn time(seconds: f32, glyphs_per_second: f32) -> Self {
+        Reveal::glyphs(seconds * glyphs_per_second)
+    }
+
+    /// Returns an identical `Reveal` fading the edge in over a number of
+    /// glyphs.
+    #[inline]
+    pub fn fade(self, fade: f32) -> Self {
+        Reveal { fade, ..self }
+    }
+
+    /// Returns the opacity of the glyph at a position in text order.
+    pub(crate) fn alpha(&self, index: usize) -> f32 {
+        let revealed = self.glyphs - index as f32;
+        match self.fade {
+            fade if fade > 0.0 => (revealed / fade).clamp(0.0, 1.0),
+            _ if revealed >= 1.0 => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the opacity of each glyph, revealed in text order whatever
+    /// the visual order of glyphs.
+    pub(crate) fn alphas(&self, glyphs: &[SectionGlyph]) -> Vec<f32> {
+        let mut order: Vec<_> = (0..glyphs.len()).collect();
+        order.sort_by_key(|&index| (glyphs[index].section_index, glyphs[index].byte_index));
+        let mut alphas = vec![0.0; glyphs.len()];
+        for (position, index) in order.into_iter().enumerate() {
+            alphas[index] = self.alpha(position);
+        }
+        alphas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ab_glyph::FontRef, ShapedLayout, TextDirection};
+    use glyph_brush::{FontId, GlyphPositioner, SectionGeometry, SectionText};
+
+    const FONT: &[u8] = include_bytes!("../examples/Inconsolata-Regular.ttf");
+
+    #[test]
+    fn fades_the_edge_in() {
+        let typed = Reveal::glyphs(2.5);
+        assert_eq!(typed.alpha(1), 1.0);
+        assert_eq!(typed.alpha(2), 0.0);
+
+        let fading = typed.fade(2.0);
+        assert_eq!(fading.alpha(0), 1.0);
+        assert_eq!(fading.alpha(1), 0.75);
+        assert_eq!(fading.alpha(2), 0.25);
+        assert_eq!(fading.alpha(3), 0.0);
+
+        assert_eq!(Reveal::time(0.5, 4.0), Reveal::glyphs(2.0));
+    }
+
+    #[test]
+    fn reveals_right_to_left_text_in_text_order() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        let text = "ab \u{5d0}\u{5d1}\u{5d2}";
+        let layout = ShapedLayout::default_wrap().direction(TextDirection::RightToLeft);
+        let glyphs = layout.calculate_glyphs(
+            &[font],
+            &SectionGeometry::default(),
+            &[SectionText {
+                text,
+                scale: 20.0.into(),
+                font_id: FontId(0),
+            }],
+        );
+
+        // the first 4 characters, up to the first hebrew letter
+        let alphas = Reveal::glyphs(4.0).alphas(&glyphs);
+        let mut revealed: Vec<_> = glyphs
+            .iter()
+            .zip(alphas)
+            .filter(|(_, alpha)| *alpha > 0.0)
+            .map(|(sg, _)| sg.byte_index)
+            .collect();
+        revealed.sort_unstable();
+        assert_eq!(revealed, [0, 1, 2, 3]);
+
+        // the first hebrew letter is drawn rightmost of its run
+        let x_of = |byte_index| {
+            glyphs
+                .iter()
+                .find(|sg| sg.byte_index == byte_index)
+                .map(|sg| sg.glyph.position.x)
+                .unwrap()
+        };
+        assert!(x_of(3) > x_of(5) && x_of(5) > x_of(7));
+    }
+}
@@ -0,0 +1,94 @@
+uniform mat4 transform;
+// per-section transforms of 4 column texels each, 256 to a row
+uniform sampler2D section_transforms;
+// camera of world-space sections
+uniform mat4 view;
+uniform mat4 projection;
+// time in seconds of glyph effects
+uniform float time;
+
+const float TAU = 6.28318530718;
+
+float random(vec2 seed) {
+    return fract(sin(dot(seed, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+vec3 hue(float h) {
+    return clamp(abs(mod(h * 6.0 + vec3(0.0, 4.0, 2.0), 6.0) - 3.0) - 1.0, 0.0, 1.0);
+}
+
+// fade & animate a quad corner of a glyph at phase_x along its text
+vec2 animate(
+    vec2 pos,
+    float phase_x,
+    vec2 fade,
+    uint effect,
+    vec4 effect_params,
+    inout vec4 color
+) {
+    // fade out from x = fade.x to fade.y, alpha interpolating across glyphs
+    if (fade.y > fade.x) {
+        color.a *= clamp((fade.y - pos.x) / (fade.y - fade.x), 0.0, 1.0);
+    }
+
+    switch (effect) {
+        case 1u: {
+            // wave of amplitude, wavelength & speed
+            float phase = phase_x / effect_params.y - effect_params.z * time;
+            pos.y += effect_params.x * sin(TAU * phase);
+            break;
+        }
+
+        case 2u: {
+            // shake of amplitude & rate
+            float tick = floor(time * effect_params.y);
+            vec2 jitter = vec2(random(vec2(phase_x, tick)), random(vec2(tick, phase_x)));
+            pos += effect_params.x * (jitter * 2.0 - 1.0);
+            break;
+        }
+
+        case 3u: {
+            // rainbow of wavelength & speed
+            color.rgb = hue(fract(phase_x / effect_params.x - effect_params.y * time));
+            break;
+        }
+    }
+
+    return pos;
+}
+
+// place a quad corner in pixels with the section's transform
+vec4 project(vec2 pos, float z, uint transform_index, uint billboard) {
+    ivec2 texel = ivec2(int(transform_index % 256u) * 4, int(transform_index / 256u));
+    mat4 section_transform = mat4(
+        texelFetch(section_transforms, texel, 0),
+        texelFetch(section_transforms, texel + ivec2(1, 0), 0),
+        texelFetch(section_transforms, texel + ivec2(2, 0), 0),
+        texelFetch(section_transforms, texel + ivec2(3, 0), 0)
+    );
+
+    // world-space sections map pixels to world units, y pointing up
+    vec4 local = vec4(pos.x, -pos.y, z, 1.0);
+
+    switch (billboard) {
+        case 1u:
+            // fixed orientation in the world
+            return projection * view * section_transform * local;
+
+        case 2u: {
+            // facing the camera, offset from the anchor in view space
+            vec3 offset = mat3(section_transform) * local.xyz;
+            return projection * (view * section_transform[3] + vec4(offset, 0.0));
+        }
+
+        case 3u: {
+            // facing the camera at a constant size, offset in pixels
+            vec4 anchor = projection * view * section_transform[3];
+            anchor.xy += (transform * vec4(pos, 0.0, 0.0)).xy * anchor.w;
+            return anchor;
+        }
+
+        default:
+            return transform * section_transform * vec4(pos, z, 1.0);
+    }
+}
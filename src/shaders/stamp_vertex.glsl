@@ -0,0 +1,40 @@
+layout(location = 0) in vec3 stamp_position;
+layout(location = 1) in vec2 stamp_tex_position;
+layout(location = 2) in vec4 stamp_color;
+layout(location = 3) in vec2 stamp_fade;
+layout(location = 4) in float stamp_phase;
+layout(location = 5) in vec4 stamp_local_transform;
+layout(location = 6) in vec2 stamp_pivot;
+layout(location = 7) in uint stamp_transform_index;
+layout(location = 8) in uint stamp_billboard;
+layout(location = 9) in uint stamp_effect;
+layout(location = 10) in vec4 stamp_effect_params;
+layout(location = 11) in vec2 copy_offset;
+layout(location = 12) in vec4 copy_color;
+layout(location = 13) in float copy_scale;
+
+out vec2 f_tex_pos;
+out vec4 f_color;
+
+// place a glyph quad corner of the stamped layout for each copy, sheared &
+// rotated as laid out
+void main() {
+    f_tex_pos = stamp_tex_position;
+    f_color = stamp_color;
+
+    vec2 pos = animate(
+        stamp_position.xy,
+        stamp_phase,
+        stamp_fade,
+        stamp_effect,
+        stamp_effect_params,
+        f_color
+    );
+    f_color *= copy_color;
+
+    // rotate & scale about the pivot, then move the whole section to the copy
+    pos = stamp_pivot + mat2(stamp_local_transform.xy, stamp_local_transform.zw) * (pos - stamp_pivot);
+    pos = pos * copy_scale + copy_offset;
+
+    gl_Position = project(pos, stamp_position.z, stamp_transform_index, stamp_billboard);
+}
@@ -1,10 +1,18 @@
-uniform mat4 transform;
-
 layout(location = 0) in vec3 left_top;
 layout(location = 1) in vec2 right_bottom;
 layout(location = 2) in vec2 tex_left_top;
 layout(location = 3) in vec2 tex_right_bottom;
 layout(location = 4) in vec4 color;
+layout(location = 5) in float skew;
+layout(location = 6) in vec2 fade;
+layout(location = 7) in float rotation;
+layout(location = 8) in vec4 local_transform;
+layout(location = 9) in vec2 pivot;
+layout(location = 10) in uint transform_index;
+layout(location = 11) in uint billboard;
+layout(location = 12) in uint effect;
+layout(location = 13) in vec4 effect_params;
+layout(location = 14) in float baseline;
 
 out vec2 f_tex_pos;
 out vec4 f_color;
@@ -39,6 +47,23 @@ void main() {
             break;
     }
 
+    // synthetic oblique shears rightwards above the baseline, y pointing down
+    pos.x += skew * (baseline - pos.y);
+
+    // rotate clockwise on screen about the quad center, e.g. vertical text
+    if (rotation != 0.0) {
+        vec2 center = vec2(left + right, top + bottom) * 0.5;
+        vec2 d = pos - center;
+        float s = sin(rotation);
+        float c = cos(rotation);
+        pos = center + vec2(c * d.x - s * d.y, s * d.x + c * d.y);
+    }
+
     f_color = color;
-    gl_Position = transform * vec4(pos, left_top.z, 1.0);
-}
\ No newline at end of file
+    pos = animate(pos, left_top.x, fade, effect, effect_params, f_color);
+
+    // rotate & scale about the pivot, e.g. a whole section
+    pos = pivot + mat2(local_transform.xy, local_transform.zw) * (pos - pivot);
+
+    gl_Position = project(pos, left_top.z, transform_index, billboard);
+}
@@ -0,0 +1,52 @@
+/// A copy of a stamped section, see
+/// [`GlyphBrush::queue_stamped`](struct.GlyphBrush.html#method.queue_stamped).
+///
+/// ```
+/// # use luminance_glyph::Stamp;
+/// let damage = Stamp::new(320.0, 96.0)
+///     .color([1.0, 0.2, 0.2, 0.8])
+///     .scale(1.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stamp {
+    /// Position in pixels the section's `screen_position` is moved to.
+    pub position: (f32, f32),
+    /// Color multiplying the section's text colors.
+    pub color: [f32; 4],
+    /// Scale factor about the position.
+    pub scale: f32,
+}
+
+impl Default for Stamp {
+    #[inline]
+    fn default() -> Self {
+        Stamp {
+            position: (0.0, 0.0),
+            color: [1.0; 4],
+            scale: 1.0,
+        }
+    }
+}
+
+impl Stamp {
+    /// Returns an unscaled, untinted copy at a position in pixels.
+    #[inline]
+    pub fn new(x: f32, y: f32) -> Self {
+        Stamp {
+            position: (x, y),
+            ..Stamp::default()
+        }
+    }
+
+    /// Returns an identical `Stamp` with a new color.
+    #[inline]
+    pub fn color(self, color: [f32; 4]) -> Self {
+        Stamp { color, ..self }
+    }
+
+    /// Returns an identical `Stamp` with a new scale factor.
+    #[inline]
+    pub fn scale(self, scale: f32) -> Self {
+        Stamp { scale, ..self }
+    }
+}
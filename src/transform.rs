@@ -0,0 +1,304 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+/// Rotation & scale of a queued section about a pivot, applied to its glyph
+/// quads when drawn, see
+/// [`GlyphBrush::queue_transformed`](struct.GlyphBrush.html#method.queue_transformed).
+///
+/// Layout, bounds & clipping happen untransformed, so the bounds turn with
+/// the text.
+///
+/// ```
+/// # use luminance_glyph::SectionTransform;
+/// let tilted = SectionTransform::default()
+///     .rotation(-0.3)
+///     .scale(1.5, 1.5)
+///     .pivot(200.0, 120.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SectionTransform {
+    /// Clockwise rotation on screen in radians.
+    pub rotation: f32,
+    /// Horizontal & vertical scale factors, applied before rotating.
+    pub scale: (f32, f32),
+    /// Point in pixels the section is rotated & scaled about, its
+    /// `screen_position` when `None`.
+    pub pivot: Option<(f32, f32)>,
+}
+
+impl Default for SectionTransform {
+    #[inline]
+    fn default() -> Self {
+        SectionTransform {
+            rotation: 0.0,
+            scale: (1.0, 1.0),
+            pivot: None,
+        }
+    }
+}
+
+impl SectionTransform {
+    /// Returns an identical `SectionTransform` with a new clockwise rotation
+    /// in radians.
+    #[inline]
+    pub fn rotation(self, rotation: f32) -> Self {
+        SectionTransform { rotation, ..self }
+    }
+
+    /// Returns an identical `SectionTransform` with new scale factors.
+    #[inline]
+    pub fn scale(self, x: f32, y: f32) -> Self {
+        SectionTransform {
+            scale: (x, y),
+            ..self
+        }
+    }
+
+    /// Returns an identical `SectionTransform` rotating & scaling about a
+    /// point in pixels.
+    #[inline]
+    pub fn pivot(self, x: f32, y: f32) -> Self {
+        SectionTransform {
+            pivot: Some((x, y)),
+            ..self
+        }
+    }
+
+    /// Returns the column-major 2x2 matrix scaling then rotating.
+    pub(crate) fn matrix(&self) -> [f32; 4] {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (x, y) = self.scale;
+        [cos * x, sin * x, -sin * y, cos * y]
+    }
+}
+
+impl PartialEq for SectionTransform {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.rotation.to_bits() == other.rotation.to_bits()
+            && self.scale.0.to_bits() == other.scale.0.to_bits()
+            && self.scale.1.to_bits() == other.scale.1.to_bits()
+            && self.pivot.map(|(x, y)| (x.to_bits(), y.to_bits()))
+                == other.pivot.map(|(x, y)| (x.to_bits(), y.to_bits()))
+    }
+}
+
+impl Eq for SectionTransform {}
+
+impl Hash for SectionTransform {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rotation.to_bits().hash(state);
+        self.scale.0.to_bits().hash(state);
+        self.scale.1.to_bits().hash(state);
+        self.pivot
+            .map(|(x, y)| (x.to_bits(), y.to_bits()))
+            .hash(state);
+    }
+}
+
+/// How a section queued at a 3D world position with
+/// [`GlyphBrush::queue_world`](struct.GlyphBrush.html#method.queue_world)
+/// is oriented & sized.
+///
+/// Section pixel coordinates map to offsets from the anchor with y pointing
+/// up, so a section at `(0.0, 0.0)` with centered alignments is centered on
+/// it. `Extra::z` offsets glyphs along the label's normal.
+#[derive(Debug, Clone, Copy)]
+pub enum Billboard {
+    /// Fixed in the world, rotated by a column-major 4x4 matrix whose
+    /// translation is ignored, `scale` world units per pixel.
+    Fixed { orientation: [f32; 16], scale: f32 },
+    /// Facing the camera, `scale` world units per pixel, so that labels
+    /// shrink with distance.
+    FacingCamera { scale: f32 },
+    /// Facing the camera at a constant size in pixels, at the anchor's depth.
+    ScreenSize,
+}
+
+impl Billboard {
+    /// Returns the vertex shader mode, see `Instance::billboard`.
+    pub(crate) fn mode(&self) -> u32 {
+        match self {
+            Billboard::Fixed { .. } => 1,
+            Billboard::FacingCamera { .. } => 2,
+            Billboard::ScreenSize => 3,
+        }
+    }
+
+    /// Returns the model matrix placing the label at the anchor.
+    pub(crate) fn model(&self, anchor: [f32; 3]) -> [f32; 16] {
+        let (orientation, scale) = match *self {
+            Billboard::Fixed { orientation, scale } => (orientation, scale),
+            Billboard::FacingCamera { scale } => (IDENTITY, scale),
+            Billboard::ScreenSize => (IDENTITY, 1.0),
+        };
+
+        let mut model = [0.0; 16];
+        for column in 0..3 {
+            for row in 0..3 {
+                model[column * 4 + row] = orientation[column * 4 + row] * scale;
+            }
+        }
+        model[12..15].copy_from_slice(&anchor);
+        model[15] = 1.0;
+        model
+    }
+}
+
+impl PartialEq for Billboard {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.mode() == other.mode() && self.model([0.0; 3]) == other.model([0.0; 3])
+    }
+}
+
+#[rustfmt::skip]
+pub(crate) const IDENTITY: [f32; 16] = [
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 1.0, 0.0,
+    0.0, 0.0, 0.0, 1.0,
+];
+
+/// 4x4 transforms of the sections queued this frame, uploaded for drawing.
+#[derive(Debug, Default)]
+pub(crate) struct TransformQueue {
+    matrices: Vec<[f32; 16]>,
+    indices: HashMap<[u32; 16], u32>,
+}
+
+impl TransformQueue {
+    /// Returns the index of the transform, `0` being the identity, queueing
+    /// it if new this frame.
+    pub fn index(&mut self, matrix: [f32; 16]) -> u32 {
+        let matrices = &mut self.matrices;
+        *self
+            .indices
+            .entry(matrix.map(f32::to_bits))
+            .or_insert_with(|| {
+                matrices.push(matrix);
+                matrices.len() as u32
+            })
+    }
+
+    /// Returns the transforms of indices from `1`, clearing the queue.
+    pub fn take(&mut self) -> Vec<[f32; 16]> {
+        self.indices.clear();
+        std::mem::take(&mut self.matrices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    /// Transforms a point about the pivot as the vertex shader does.
+    fn apply(transform: &SectionTransform, pivot: (f32, f32), (x, y): (f32, f32)) -> (f32, f32) {
+        let [a, b, c, d] = transform.matrix();
+        let (dx, dy) = (x - pivot.0, y - pivot.1);
+        (pivot.0 + a * dx + c * dy, pivot.1 + b * dx + d * dy)
+    }
+
+    fn assert_near((x, y): (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (x - expected.0).abs() < 1e-5 && (y - expected.1).abs() < 1e-5,
+            "{:?} != {:?}",
+            (x, y),
+            expected
+        );
+    }
+
+    #[test]
+    fn scales_then_rotates_clockwise_about_the_pivot() {
+        let identity = SectionTransform::default();
+        assert_eq!(identity.matrix(), [1.0, 0.0, 0.0, 1.0]);
+
+        // y points down, so a quarter turn takes right to down
+        let turned = SectionTransform::default().rotation(FRAC_PI_2);
+        assert_near(apply(&turned, (0.0, 0.0), (1.0, 0.0)), (0.0, 1.0));
+        assert_near(apply(&turned, (10.0, 10.0), (11.0, 10.0)), (10.0, 11.0));
+
+        let scaled = turned.scale(2.0, 3.0);
+        assert_near(apply(&scaled, (0.0, 0.0), (1.0, 0.0)), (0.0, 2.0));
+        assert_near(apply(&scaled, (0.0, 0.0), (0.0, 1.0)), (-3.0, 0.0));
+    }
+
+    #[test]
+    fn compares_transforms_bitwise() {
+        let transform = SectionTransform::default().rotation(0.5).pivot(1.0, 2.0);
+        assert_eq!(transform, transform.rotation(0.5));
+        assert_ne!(transform, SectionTransform::default().rotation(0.5));
+        assert_ne!(transform, transform.scale(-1.0, 1.0));
+    }
+
+    #[test]
+    fn queues_each_distinct_transform_once() {
+        let mut translate = IDENTITY;
+        translate[12] = 5.0;
+        let mut queue = TransformQueue::default();
+
+        assert_eq!(queue.index(translate), 1);
+        assert_eq!(queue.index(IDENTITY), 2);
+        assert_eq!(queue.index(translate), 1);
+        assert_eq!(queue.take(), [translate, IDENTITY]);
+
+        // indices restart each frame
+        assert_eq!(queue.index(IDENTITY), 1);
+        assert_eq!(queue.take(), [IDENTITY]);
+        assert!(queue.take().is_empty());
+    }
+
+    #[test]
+    fn places_billboards_at_their_anchor() {
+        let anchor = [1.0, 2.0, 3.0];
+        let model = Billboard::FacingCamera { scale: 0.5 }.model(anchor);
+        assert_eq!(&model[..4], [0.5, 0.0, 0.0, 0.0]);
+        assert_eq!(&model[8..], [0.0, 0.0, 0.5, 0.0, 1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(Billboard::ScreenSize.model(anchor)[..12], IDENTITY[..12]);
+
+        // fixed labels keep the rotation of their orientation but not its
+        // translation
+        #[rustfmt::skip]
+        let quarter_turn = [
+            0.0, 1.0, 0.0, 0.0,
+            -1.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            9.0, 9.0, 9.0, 1.0,
+        ];
+        let model = Billboard::Fixed {
+            orientation: quarter_turn,
+            scale: 2.0,
+        }
+        .model(anchor);
+        assert_eq!(&model[..8], [0.0, 2.0, 0.0, 0.0, -2.0, 0.0, 0.0, 0.0]);
+        assert_eq!(&model[12..], [1.0, 2.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn compares_billboards_by_mode_and_orientation() {
+        assert_eq!(
+            Billboard::FacingCamera { scale: 1.0 },
+            Billboard::FacingCamera { scale: 1.0 }
+        );
+        assert_ne!(
+            Billboard::FacingCamera { scale: 1.0 },
+            Billboard::FacingCamera { scale: 2.0 }
+        );
+        // same model, drawn differently
+        assert_ne!(
+            Billboard::FacingCamera { scale: 1.0 },
+            Billboard::ScreenSize
+        );
+        assert_ne!(
+            Billboard::Fixed {
+                orientation: IDENTITY,
+                scale: 1.0
+            },
+            Billboard::ScreenSize
+        );
+    }
+}